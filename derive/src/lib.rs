@@ -0,0 +1,76 @@
+//! Derive macros of the `metaheuristics-nature` crate.
+//!
+//! Please use the re-exported macros with the `derive` feature of
+//! `metaheuristics-nature`.
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, spanned::Spanned as _, Data, DeriveInput, Error, Fields};
+
+/// Implement `Fitness` and `MultiObjective` for a struct with the
+/// `#[objective]` fields.
+///
+/// See the re-exported document in `metaheuristics-nature`.
+#[proc_macro_derive(MultiObjective, attributes(objective))]
+pub fn multi_objective(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match impl_multi_objective(input) {
+        Ok(ts) => ts.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+fn impl_multi_objective(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new(input.span(), "expect a struct"));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(Error::new(input.span(), "expect named fields"));
+    };
+    let obj = fields
+        .named
+        .iter()
+        .filter(|f| f.attrs.iter().any(|a| a.path().is_ident("objective")))
+        .map(|f| f.ident.as_ref().unwrap())
+        .collect::<Vec<_>>();
+    if obj.is_empty() {
+        return Err(Error::new(
+            input.span(),
+            "expect at least one `#[objective]` field",
+        ));
+    }
+    let name = &input.ident;
+    let (impl_g, ty_g, where_clause) = input.generics.split_for_impl();
+    let root = quote!(::metaheuristics_nature);
+    Ok(quote! {
+        impl #impl_g #root::Fitness for #name #ty_g #where_clause {
+            type Best<T: #root::Fitness> = #root::pareto::Pareto<T>;
+            type Eval = f64;
+            fn is_dominated(&self, rhs: &Self) -> bool {
+                // Not worse in all objectives, and better in at least one
+                let mut better = false;
+                #(
+                    let (a, b) = (self.#obj as f64, rhs.#obj as f64);
+                    if a > b {
+                        return false;
+                    }
+                    better |= a < b;
+                )*
+                better
+            }
+            fn eval(&self) -> Self::Eval {
+                let mut eval = f64::NEG_INFINITY;
+                #(eval = eval.max(self.#obj as f64);)*
+                eval
+            }
+            fn objective_values(&self) -> ::core::option::Option<#root::__private::Vec<f64>> {
+                ::core::option::Option::Some(#root::MultiObjective::objectives(self))
+            }
+        }
+
+        impl #impl_g #root::MultiObjective for #name #ty_g #where_clause {
+            fn objectives(&self) -> #root::__private::Vec<f64> {
+                #root::__private::Vec::from([#(self.#obj as f64),*])
+            }
+        }
+    })
+}
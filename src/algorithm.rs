@@ -1,4 +1,5 @@
 use crate::prelude::*;
+use alloc::{boxed::Box, vec::Vec};
 
 /// Algorithm configurations. A trait for preparing the algorithm.
 ///
@@ -14,6 +15,14 @@ pub trait AlgCfg {
     fn pop_num() -> usize {
         200
     }
+    /// Create the boxed algorithm for the dynamic dispatching, such as the
+    /// islands of [`Solver::build_islands()`](crate::Solver::build_islands).
+    fn boxed<F: ObjFunc>(self) -> maybe_send_box!(Algorithm<F>)
+    where
+        Self: Sized,
+    {
+        Box::new(self.algorithm())
+    }
 }
 
 /// The methods of the metaheuristic algorithms.
@@ -102,3 +111,81 @@ impl<F: ObjFunc, T: Algorithm<F> + ?Sized> Algorithm<F> for alloc::boxed::Box<T>
         self.as_mut().generation(ctx, rng);
     }
 }
+
+/// A composite algorithm that runs several algorithms in sequence.
+///
+/// Each stage is active for a fraction of the maximum generation number (see
+/// [`Ctx::max_gen()`]). If it is unknown, the fractions are taken from 100
+/// generations. The last stage keeps running after all fractions are passed.
+///
+/// A stage is initialized when it becomes active, and its state is kept until
+/// the next stage.
+///
+/// ```
+/// use metaheuristics_nature::{De, Rga, Sequential, Solver};
+/// # use metaheuristics_nature::tests::TestObj as MyFunc;
+///
+/// // Coarse search with DE, then fine search with RGA
+/// let seq = Sequential::new(De::default(), 0.5).stage(Rga::default(), 0.5);
+/// let s = Solver::build_sequential(seq, MyFunc::new())
+///     .seed(0)
+///     .task_gen(20)
+///     .solve()
+///     .unwrap();
+/// ```
+pub struct Sequential<F: ObjFunc> {
+    stages: Vec<(maybe_send_box!(Algorithm<F>), f64)>,
+    pop_num: usize,
+    active: usize,
+}
+
+impl<F: ObjFunc> Sequential<F> {
+    /// Create a sequence with the first stage, the algorithm setting and its
+    /// generation fraction.
+    ///
+    /// The default population number is decided by the first stage.
+    pub fn new<A: AlgCfg>(cfg: A, fraction: f64) -> Self {
+        let stages = alloc::vec![(cfg.boxed(), fraction)];
+        Self { stages, pop_num: A::pop_num(), active: 0 }
+    }
+
+    /// Append a stage with the algorithm setting and its generation fraction.
+    pub fn stage<A: AlgCfg>(mut self, cfg: A, fraction: f64) -> Self {
+        self.stages.push((cfg.boxed(), fraction));
+        self
+    }
+
+    /// Default population number, see [`Sequential::new()`].
+    pub fn pop_num(&self) -> usize {
+        self.pop_num
+    }
+
+    // Find the active stage by the generation (starts from 1)
+    fn current(&self, ctx: &Ctx<F>) -> usize {
+        let ratio = ctx.gen.saturating_sub(1) as f64 / ctx.max_gen().unwrap_or(100) as f64;
+        let mut end = 0.;
+        self.stages
+            .iter()
+            .position(|(_, fraction)| {
+                end += fraction;
+                ratio < end
+            })
+            .unwrap_or(self.stages.len() - 1)
+    }
+}
+
+impl<F: ObjFunc> Algorithm<F> for Sequential<F> {
+    fn init(&mut self, ctx: &mut Ctx<F>, rng: &mut Rng) {
+        self.active = self.current(ctx);
+        self.stages[self.active].0.init(ctx, rng);
+    }
+
+    fn generation(&mut self, ctx: &mut Ctx<F>, rng: &mut Rng) {
+        let stage = self.current(ctx);
+        if stage != self.active {
+            self.active = stage;
+            self.stages[stage].0.init(ctx, rng);
+        }
+        self.stages[stage].0.generation(ctx, rng);
+    }
+}
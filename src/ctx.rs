@@ -1,7 +1,28 @@
 use crate::prelude::*;
 use alloc::vec::Vec;
+use core::{
+    iter::zip,
+    sync::atomic::{AtomicUsize, Ordering},
+};
 
 pub(crate) type BestCon<F> = <F as Fitness>::Best<F>;
+/// The number of samples and the averaging function, see
+/// [`SolverBuilder::resample()`].
+pub(crate) type Resampling<Y> = Option<(usize, fn(&[Y]) -> Y)>;
+
+/// The statistics of the evaluation values of the pool.
+///
+/// Created by [`Ctx::snapshot_stats()`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PoolStats {
+    /// Minimum evaluation value
+    pub min: f64,
+    /// Mean of the evaluation values
+    pub mean: f64,
+    /// Maximum evaluation value
+    pub max: f64,
+}
 
 /// A basic context type of the algorithms.
 ///
@@ -17,6 +38,9 @@ pub(crate) type BestCon<F> = <F as Fitness>::Best<F>;
 /// + `ctx.pop_num()` - Get population number.
 /// + `ctx.best.get_eval()` - Get the current best evaluation value.
 /// + `ctx.best.get_xs()` - Get the current best variables.
+/// + `ctx.eval_count()` - Get the number of objective function calls.
+/// + `ctx.diversity()` - Get the population diversity.
+/// + `ctx.snapshot_stats()` - Get the statistics of the evaluation values.
 ///
 /// # Implement an Algorithm
 ///
@@ -34,6 +58,25 @@ pub struct Ctx<F: ObjFunc> {
     pub func: F,
     /// Generation (iteration) number
     pub gen: u64,
+    pub(crate) max_gen: Option<u64>,
+    pub(crate) resample: Resampling<F::Ys>,
+    eval_count: AtomicUsize,
+    stat: Progress<<F::Ys as Fitness>::Eval>,
+    #[cfg(feature = "std")]
+    pub(crate) surrogate: Option<SurrogateCtx<F::Ys>>,
+}
+
+/// The surrogate model and the true evaluations, see
+/// [`SolverBuilder::surrogate()`].
+#[cfg(feature = "std")]
+pub(crate) struct SurrogateCtx<Y: Fitness> {
+    model: alloc::boxed::Box<dyn Surrogate<Y>>,
+    ratio: f64,
+    // The best container of the true evaluations only
+    archive: BestCon<Y>,
+    clone_best: fn(&BestCon<Y>) -> BestCon<Y>,
+    // The true evaluations since the last training
+    pending: std::sync::Mutex<Vec<(Vec<f64>, Y)>>,
 }
 
 impl<F: ObjFunc> Ctx<F> {
@@ -45,16 +88,35 @@ impl<F: ObjFunc> Ctx<F> {
     ) -> Self {
         let mut best = BestCon::<F::Ys>::from_limit(limit);
         best.update_all(&pool, &pool_y);
-        Self { best, pool, pool_y, func, gen: 0 }
+        Self {
+            best,
+            pool,
+            pool_y,
+            func,
+            gen: 0,
+            max_gen: None,
+            resample: None,
+            eval_count: AtomicUsize::new(0),
+            stat: Progress::default(),
+            #[cfg(feature = "std")]
+            surrogate: None,
+        }
     }
 
-    pub(crate) fn from_pool(func: F, limit: usize, pool: Vec<Vec<f64>>) -> Self {
-        #[cfg(not(feature = "rayon"))]
-        let iter = pool.iter();
-        #[cfg(feature = "rayon")]
-        let iter = pool.par_iter();
-        let pool_y = iter.map(|xs| func.fitness(xs)).collect();
-        Self::from_parts(func, limit, pool, pool_y)
+    pub(crate) fn from_pool(
+        func: F,
+        limit: usize,
+        pool: Vec<Vec<f64>>,
+        resample: Resampling<F::Ys>,
+    ) -> Self {
+        let mut ctx = Self {
+            resample,
+            ..Self::from_parts(func, limit, Vec::new(), Vec::new())
+        };
+        ctx.pool_y = ctx.fitness_batch(&pool);
+        ctx.pool = pool;
+        ctx.find_best();
+        ctx
     }
 
     /// Get population number.
@@ -63,18 +125,419 @@ impl<F: ObjFunc> Ctx<F> {
         self.pool.len()
     }
 
+    /// Get the design variables of the pool without cloning.
+    ///
+    /// Same as the [`Ctx::pool`] field, but can be passed as a borrowed slice
+    /// to a logger.
+    #[inline]
+    pub fn pool_view(&self) -> &[Vec<f64>] {
+        &self.pool
+    }
+
+    /// Get the minimum, mean and maximum evaluation values of the pool in one
+    /// pass.
+    ///
+    /// This is a cheap way to record the progress in the
+    /// [`SolverBuilder::callback()`] instead of cloning the pool.
+    ///
+    /// Returns none if the pool is empty. The NaN values are treated as the
+    /// worst, so the maximum is NaN if there is any NaN value, and the NaN
+    /// values are excluded from the minimum and the mean. If all the values
+    /// are NaN, all the statistics are NaN.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{Rga, Solver};
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// let mut history = Vec::new();
+    /// let s = Solver::build(Rga::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .callback(|ctx| history.extend(ctx.snapshot_stats()))
+    ///     .solve()
+    ///     .unwrap();
+    /// assert!(history.iter().all(|s| s.min <= s.mean && s.mean <= s.max));
+    /// ```
+    pub fn snapshot_stats(&self) -> Option<PoolStats>
+    where
+        F::Ys: Fitness<Eval = f64>,
+    {
+        if self.pool_y.is_empty() {
+            return None;
+        }
+        let init = PoolStats {
+            min: f64::INFINITY,
+            mean: 0.,
+            max: f64::NEG_INFINITY,
+        };
+        let mut n = 0;
+        let mut stats = (self.pool_y.iter())
+            .map(|ys| ys.eval())
+            .filter(|y| !y.is_nan())
+            .fold(init, |s, y| {
+                n += 1;
+                PoolStats {
+                    min: s.min.min(y),
+                    mean: s.mean + y,
+                    max: s.max.max(y),
+                }
+            });
+        if n == 0 {
+            return Some(PoolStats { min: f64::NAN, mean: f64::NAN, max: f64::NAN });
+        }
+        stats.mean /= n as f64;
+        if n < self.pop_num() {
+            stats.max = f64::NAN;
+        }
+        Some(stats)
+    }
+
+    /// Get the maximum generation number if it is known.
+    ///
+    /// It is known if the termination condition is set by
+    /// [`SolverBuilder::task_gen()`] or the default.
+    pub fn max_gen(&self) -> Option<u64> {
+        self.max_gen
+    }
+
+    /// Get the progress ratio `gen / max_gen` in `0..=1` if the maximum
+    /// generation number is known.
+    ///
+    /// See also [`Ctx::max_gen()`].
+    pub fn progress(&self) -> Option<f64> {
+        self.max_gen
+            .map(|max_gen| (self.gen as f64 / max_gen as f64).min(1.))
+    }
+
+    /// Evaluate the fitness of the design variables.
+    ///
+    /// This method will be counted by [`Ctx::eval_count()`], please use this
+    /// method instead of calling [`ObjFunc::fitness()`] directly. The
+    /// objective function is called by [`ObjFunc::fitness_ctx()`] with the
+    /// progress of the algorithm.
+    ///
+    /// The fitness value may be predicted by the surrogate model, see
+    /// [`SolverBuilder::surrogate()`].
+    pub fn fitness(&self, xs: &[f64]) -> F::Ys {
+        #[cfg(feature = "std")]
+        if let Some(ys) = self.predict(xs) {
+            return ys;
+        }
+        self.eval_count.fetch_add(1, Ordering::Relaxed);
+        let ys = self.eval_func(xs);
+        #[cfg(feature = "std")]
+        self.record(xs, &ys);
+        ys
+    }
+
+    /// Evaluate the fitness of a batch of design variables.
+    ///
+    /// This method will be counted by [`Ctx::eval_count()`], please use this
+    /// method instead of calling [`ObjFunc::fitness_batch()`] directly.
+    ///
+    /// The designs are evaluated in parallel if the `rayon` feature is enabled
+    /// (unless the objective function overrides [`ObjFunc::fitness_batch()`]),
+    /// so prefer this method over a loop of [`Ctx::fitness()`] when the trials
+    /// are generated at once.
+    pub fn fitness_batch(&self, pool: &[Vec<f64>]) -> Vec<F::Ys> {
+        #[cfg(feature = "std")]
+        if self.surrogate.is_some() {
+            return self.fitness_batch_surrogate(pool);
+        }
+        self.eval_count.fetch_add(pool.len(), Ordering::Relaxed);
+        self.eval_func_batch(pool)
+    }
+
+    // Evaluate by the objective function, with the resampling if it is set
+    fn eval_func(&self, xs: &[f64]) -> F::Ys {
+        match self.resample {
+            Some((n, average)) => resample_fitness(&self.func, xs, &self.stat, n, average),
+            None => self.func.fitness_ctx(xs, &self.stat),
+        }
+    }
+
+    fn eval_func_batch(&self, pool: &[Vec<f64>]) -> Vec<F::Ys> {
+        match self.resample {
+            Some((n, average)) => resample_batch(&self.func, pool, &self.stat, n, average),
+            None => self.func.fitness_batch(pool, &self.stat),
+        }
+    }
+
+    #[cfg(feature = "std")]
+    fn fitness_batch_surrogate(&self, pool: &[Vec<f64>]) -> Vec<F::Ys> {
+        #[cfg(not(feature = "rayon"))]
+        let iter = pool.iter();
+        #[cfg(feature = "rayon")]
+        let iter = pool.par_iter();
+        let mut pool_y = iter.map(|xs| self.predict(xs)).collect::<Vec<_>>();
+        let ind = (0..pool.len())
+            .filter(|&i| pool_y[i].is_none())
+            .collect::<Vec<_>>();
+        let trials = ind.iter().map(|&i| pool[i].clone()).collect::<Vec<_>>();
+        self.eval_count.fetch_add(trials.len(), Ordering::Relaxed);
+        let trials_y = self.eval_func_batch(&trials);
+        for (i, ys) in zip(ind, trials_y) {
+            self.record(&pool[i], &ys);
+            pool_y[i] = Some(ys);
+        }
+        pool_y.into_iter().map(Option::unwrap).collect()
+    }
+
+    // Return the prediction if the design can skip the true evaluation
+    #[cfg(feature = "std")]
+    fn predict(&self, xs: &[f64]) -> Option<F::Ys> {
+        let s = self.surrogate.as_ref()?;
+        let ys = s.model.predict(xs)?;
+        let promising = match &self.stat.best_eval {
+            Some(best) => &ys.eval() < best,
+            None => true,
+        };
+        // Pick the designs by their variables instead of the calling order
+        (!promising && hash_unit(xs) >= s.ratio).then_some(ys)
+    }
+
+    #[cfg(feature = "std")]
+    fn record(&self, xs: &[f64], ys: &F::Ys) {
+        if let Some(s) = &self.surrogate {
+            // The pending list is always valid even if a thread is panicked
+            let mut pending = s.pending.lock().unwrap_or_else(|e| e.into_inner());
+            pending.push((xs.to_vec(), ys.clone()));
+        }
+    }
+
+    // Attach the surrogate model, the current pool is the true evaluations
+    #[cfg(feature = "std")]
+    pub(crate) fn set_surrogate(
+        &mut self,
+        mut model: alloc::boxed::Box<dyn Surrogate<F::Ys>>,
+        ratio: f64,
+        clone_best: fn(&BestCon<F::Ys>) -> BestCon<F::Ys>,
+    ) {
+        for (xs, ys) in zip(&self.pool, &self.pool_y) {
+            model.update(xs, ys);
+        }
+        let archive = clone_best(&self.best);
+        let pending = Default::default();
+        self.surrogate = Some(SurrogateCtx { model, ratio, archive, clone_best, pending });
+    }
+
+    // Train the surrogate model with the true evaluations, and reset the best
+    // container to drop the predicted values
+    #[cfg(feature = "std")]
+    pub(crate) fn train_surrogate(&mut self) {
+        let Some(s) = &mut self.surrogate else {
+            return;
+        };
+        let pending = s.pending.get_mut().unwrap_or_else(|e| e.into_inner());
+        let mut pending = core::mem::take(pending);
+        // The same training order regardless of the evaluation order
+        pending.sort_by(|(a, _), (b, _)| {
+            (a.iter().map(|x| x.to_bits())).cmp(b.iter().map(|x| x.to_bits()))
+        });
+        for (xs, ys) in &pending {
+            s.model.update(xs, ys);
+            s.archive.update(xs, ys);
+        }
+        self.best = (s.clone_best)(&s.archive);
+    }
+
+    // Update the progress information for the objective function
+    pub(crate) fn update_progress(&mut self) {
+        self.stat = Progress {
+            gen: self.gen,
+            best_eval: Some(self.best.get_eval()),
+            ratio: self.progress(),
+        };
+    }
+
+    /// Get the number of objective function calls.
+    ///
+    /// The evaluations of the initial pool are included, except the
+    /// [`Pool::Ready`] option which is provided by the user. The values
+    /// predicted by the surrogate model are not included.
+    pub fn eval_count(&self) -> usize {
+        self.eval_count.load(Ordering::Relaxed)
+    }
+
+    /// Get the population diversity.
+    ///
+    /// The diversity is the mean distance between the individuals and their
+    /// centroid, where each variable is normalized by its bound width. A
+    /// converged population has a diversity close to zero.
+    pub fn diversity(&self) -> f64 {
+        let n = self.pop_num() as f64;
+        let center = (0..self.dim())
+            .map(|s| self.pool.iter().map(|xs| xs[s]).sum::<f64>() / n)
+            .collect::<Vec<_>>();
+        let sum = (self.pool.iter())
+            .map(|xs| {
+                (0..self.dim())
+                    .map(|s| {
+                        let w = self.bound_width(s);
+                        if w > 0. {
+                            ((xs[s] - center[s]) / w).powi(2)
+                        } else {
+                            0.
+                        }
+                    })
+                    .sum::<f64>()
+                    .sqrt()
+            })
+            .sum::<f64>();
+        sum / n
+    }
+
+    /// Get the indices of the individuals sorted from the best to the worst.
+    ///
+    /// The feasible individuals come first, and then the smaller evaluation
+    /// values (see [`Fitness::is_feasible()`]). If the evaluation values are
+    /// tied, the individual with the lower index is preferred.
+    pub fn rank(&self) -> Vec<usize> {
+        let mut ind = (0..self.pop_num()).collect::<Vec<_>>();
+        ind.sort_by(|a, b| cmp_fit(&self.pool_y[*a], &self.pool_y[*b]));
+        ind
+    }
+
+    /// Get the indices of the best `k` individuals, see [`Ctx::rank()`].
+    pub fn best_indices(&self, k: usize) -> Vec<usize> {
+        let mut ind = self.rank();
+        ind.truncate(k);
+        ind
+    }
+
+    /// Get the index of the best individual, which has the smallest evaluation
+    /// value.
+    ///
+    /// If the evaluation values are tied, the individual with the lower index
+    /// is preferred. This method only scans the current pool, the best
+    /// container [`Ctx::best`] is not touched.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the pool is empty.
+    pub fn best_index(&self) -> usize {
+        (self.pool_y.iter().map(Fitness::eval).enumerate())
+            .min_by(|(_, a), (_, b)| cmp_eval(a, b))
+            .expect("empty pool")
+            .0
+    }
+
+    /// Get the index of the worst individual, which has the largest evaluation
+    /// value.
+    ///
+    /// If the evaluation values are tied, the individual with the higher index
+    /// is preferred, the reverse of [`Ctx::best_index()`]. This method only
+    /// scans the current pool, the best container [`Ctx::best`] is not
+    /// touched.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the pool is empty.
+    pub fn worst_index(&self) -> usize {
+        (self.pool_y.iter().map(Fitness::eval).enumerate())
+            .max_by(|(_, a), (_, b)| cmp_eval(a, b))
+            .expect("empty pool")
+            .0
+    }
+
+    /// Iterate over the individuals as the design variables and fitness value
+    /// pairs.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{Fitness, Rga, Solver};
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// let s = Solver::build(Rga::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     // Stop if the whole population is good enough
+    ///     .task(|ctx| ctx.gen == 20 || ctx.individuals().all(|(_, ys)| ys.eval() < 10.))
+    ///     .solve()
+    ///     .unwrap();
+    /// ```
+    pub fn individuals(&self) -> impl Iterator<Item = (&[f64], &F::Ys)> {
+        zip(&self.pool, &self.pool_y).map(|(xs, ys)| (xs.as_slice(), ys))
+    }
+
+    /// Iterate over the individuals as the mutable design variables and
+    /// fitness value pairs.
+    ///
+    /// Since the context is borrowed mutably, the other individuals and the
+    /// objective function cannot be accessed in the loop. Compute the trials
+    /// from [`Ctx::individuals()`] first, then write them back with this
+    /// method. Call [`Ctx::find_best()`] to update the best container after
+    /// that.
+    pub fn individuals_mut(&mut self) -> impl Iterator<Item = (&mut [f64], &mut F::Ys)> {
+        zip(&mut self.pool, &mut self.pool_y).map(|(xs, ys)| (xs.as_mut_slice(), ys))
+    }
+
     /// Assign the index from source.
     pub fn set_from(&mut self, i: usize, xs: Vec<f64>, ys: F::Ys) {
         self.pool[i] = xs;
         self.pool_y[i] = ys;
     }
 
+    /// Replace the worst individual with the candidate if the candidate
+    /// dominates it, return true if replaced.
+    ///
+    /// The worst individual is chosen by [`Ctx::worst_index()`]. Same as
+    /// [`Ctx::set_from()`], call [`Ctx::find_best()`] to update the best
+    /// container.
+    pub fn replace_worst(&mut self, xs: Vec<f64>, ys: F::Ys) -> bool {
+        if self.pool_y.is_empty() {
+            return false;
+        }
+        let i = self.worst_index();
+        if ys.is_dominated_feasible(&self.pool_y[i]) {
+            self.set_from(i, xs, ys);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Mutate the design variables with the Gaussian distribution.
+    ///
+    /// Each variable is mutated with the probability `rate`, by adding a
+    /// Gaussian step with the standard deviation `std_frac` times its bound
+    /// width. The mutated variables are clamped to the bounds. Return true if
+    /// any variable is mutated.
+    ///
+    /// The design variables should be re-evaluated after the mutation.
+    pub fn gaussian_mutate(&self, rng: &mut Rng, xs: &mut [f64], rate: f64, std_frac: f64) -> bool {
+        let mut mutated = false;
+        for (s, x) in xs.iter_mut().enumerate() {
+            if rng.maybe(rate) {
+                let step = rng.normal(0., std_frac * self.bound_width(s));
+                *x = self.clamp(s, *x + step);
+                mutated = true;
+            }
+        }
+        mutated
+    }
+
     /// Find the best, and set it globally.
+    ///
+    /// If the evaluation values are tied, the individual with the lower index
+    /// is preferred, so the result is the same with or without `rayon`.
+    /// Unordered evaluation values (such as NaN) are treated as the worst.
     pub fn find_best(&mut self) {
         self.best.update_all(&self.pool, &self.pool_y);
     }
 }
 
+// A uniform number in `0..1` hashed from the design variables
+#[cfg(feature = "std")]
+fn hash_unit(xs: &[f64]) -> f64 {
+    // FNV-1a with the SplitMix64 finalizer
+    let mut h = xs.iter().fold(0xcbf2_9ce4_8422_2325_u64, |h, x| {
+        (h ^ x.to_bits()).wrapping_mul(0x0000_0100_0000_01b3)
+    });
+    h = (h ^ (h >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    h = (h ^ (h >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    h ^= h >> 31;
+    (h >> 11) as f64 / (1_u64 << 53) as f64
+}
+
 impl<F: ObjFunc> core::ops::Deref for Ctx<F> {
     type Target = F;
     fn deref(&self) -> &Self::Target {
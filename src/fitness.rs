@@ -1,12 +1,12 @@
 use crate::prelude::*;
-use alloc::sync::Arc;
+use alloc::{sync::Arc, vec::Vec};
 
 /// Trait for dominance comparison.
 ///
-/// By default, the trait is implemented for types that implement `PartialOrd +
-/// Clone + 'static`, which means a clonable non-lifetime type comparable with
-/// `a < b` is equivalent to [`a.is_dominated(b)`](Fitness::is_dominated) for
-/// using single objective.
+/// By default, the trait is implemented for types that implement [`EvalValue`]
+/// and `Clone`, which means a clonable number type comparable with `a < b` is
+/// equivalent to [`a.is_dominated(b)`](Fitness::is_dominated) for using single
+/// objective.
 ///
 /// # Example
 ///
@@ -16,6 +16,9 @@ use alloc::sync::Arc;
 /// [`Fitness::Best`] and implement [`Fitness::eval()`] to decide the final
 /// fitness value.
 ///
+/// Hard constraints can be marked by [`Fitness::is_feasible()`], the feasible
+/// solutions always win.
+///
 /// ```
 /// use metaheuristics_nature::{pareto::Pareto, Fitness};
 ///
@@ -34,6 +37,9 @@ use alloc::sync::Arc;
 ///     fn eval(&self) -> Self::Eval {
 ///         self.cost.max(self.weight)
 ///     }
+///     fn is_feasible(&self) -> bool {
+///         self.weight < 100.
+///     }
 /// }
 /// ```
 pub trait Fitness: MaybeParallel + Clone + 'static {
@@ -42,27 +48,116 @@ pub trait Fitness: MaybeParallel + Clone + 'static {
     /// + Use [`Pareto`] for multi-objective.
     type Best<T: Fitness>: Best<Item = T>;
     /// A value to compare the final fitness value.
-    type Eval: PartialOrd + 'static;
+    type Eval: EvalValue;
     /// Check if `self` dominates `rhs`.
     fn is_dominated(&self, rhs: &Self) -> bool;
+    /// Check if `self` is a feasible solution.
+    ///
+    /// Infeasible solutions are always dominated by feasible solutions in
+    /// [`Fitness::is_dominated_feasible()`], regardless of their evaluation
+    /// values. This is used to encode hard constraints without setting the
+    /// fitness to infinity.
+    ///
+    /// The default behavior is always feasible.
+    #[inline]
+    fn is_feasible(&self) -> bool {
+        true
+    }
+    /// Check if `self` dominates `rhs` with the constraint-domination.
+    ///
+    /// 1. A feasible solution dominates an infeasible solution.
+    /// 1. Otherwise, compare them with [`Fitness::is_dominated()`].
+    ///
+    /// The provided methods and the best containers use this method for
+    /// comparison.
+    fn is_dominated_feasible(&self, rhs: &Self) -> bool {
+        match (self.is_feasible(), rhs.is_feasible()) {
+            (true, false) => true,
+            (false, true) => false,
+            _ => self.is_dominated(rhs),
+        }
+    }
     /// Evaluate the final fitness value.
     ///
     /// Used in [`Best::as_result()`] and [`Best::update()`] when reaching the
     /// limit.
     fn eval(&self) -> Self::Eval;
+    /// Get the objective values if they are available, see
+    /// [`MultiObjective`].
+    ///
+    /// The objective space is used to measure the crowding distance of the
+    /// multi-objective methods, such as [`Nsga2`] and
+    /// [`Pareto::sample_weighted()`], which fall back to the design variable
+    /// space if the objective values are not available. Please implement this
+    /// method as `Some(self.objectives())` for a multi-objective fitness type.
+    /// The `MultiObjective` derive macro (the `derive` feature) implements it
+    /// automatically.
+    ///
+    /// The default behavior is none.
+    #[inline]
+    fn objective_values(&self) -> Option<Vec<f64>> {
+        None
+    }
 }
 
-impl<T: MaybeParallel + PartialOrd + Clone + 'static> Fitness for T {
+impl<T: EvalValue + Clone> Fitness for T {
     type Best<A: Fitness> = SingleBest<A>;
     type Eval = Self;
     fn is_dominated(&self, rhs: &Self) -> bool {
-        self < rhs
+        cmp_eval(self, rhs).is_lt()
     }
     fn eval(&self) -> Self::Eval {
         self.clone()
     }
 }
 
+/// Trait for the evaluation values, see [`Fitness::Eval`].
+///
+/// This trait is implemented for the primitive number types. The custom types
+/// can implement it without [`EvalValue::to_f64()`].
+///
+/// ```
+/// use metaheuristics_nature::EvalValue;
+///
+/// #[derive(Clone, PartialEq, PartialOrd)]
+/// struct Cost(u32, f64);
+///
+/// impl EvalValue for Cost {}
+/// ```
+pub trait EvalValue: PartialOrd + MaybeParallel + 'static {
+    /// Convert the value to a floating-point number, which is required by the
+    /// fitness-proportionate selection, such as the onlooker bees of [`Abc`].
+    ///
+    /// The default behavior is none, then the selection falls back to the
+    /// rank of the values.
+    #[inline]
+    fn to_f64(&self) -> Option<f64> {
+        None
+    }
+}
+
+macro_rules! impl_eval_value {
+    ($($ty:ty),+) => {$(
+        impl EvalValue for $ty {
+            #[inline]
+            fn to_f64(&self) -> Option<f64> {
+                Some(*self as f64)
+            }
+        }
+    )+};
+}
+
+impl_eval_value!(f32, f64, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+/// Compare two evaluation values, where the unordered values (such as NaN) are
+/// treated as the worst.
+pub(crate) fn cmp_eval<T: PartialOrd>(a: &T, b: &T) -> core::cmp::Ordering {
+    // A value is unordered if it cannot be compared with itself
+    let is_nan = |v: &T| v.partial_cmp(v).is_none();
+    a.partial_cmp(b)
+        .unwrap_or_else(|| is_nan(a).cmp(&is_nan(b)))
+}
+
 /// A [`Fitness`] type carrying a multi-objective [`Fitness`] value. Make it
 /// become a single objective task via using [`Fitness::eval()`].
 ///
@@ -70,6 +165,8 @@ impl<T: MaybeParallel + PartialOrd + Clone + 'static> Fitness for T {
 /// multi-objective fitness type can be tested in single mode by setting
 /// [`ObjFunc::Ys`] to `MakeSingle<MyMOFit>` and wrapping the final result with
 /// `MakeSingle(MyMOFit { .. })`.
+///
+/// See also [`WeightedSum`] for the weighted scalarization.
 #[derive(Clone, Debug)]
 #[repr(transparent)]
 pub struct MakeSingle<Y: Fitness>(pub Y)
@@ -85,10 +182,248 @@ where
     fn is_dominated(&self, rhs: &Self) -> bool {
         self.eval().is_dominated(&rhs.eval())
     }
+    fn is_feasible(&self) -> bool {
+        self.0.is_feasible()
+    }
     #[inline]
     fn eval(&self) -> Self::Eval {
         self.0.eval()
     }
+    fn objective_values(&self) -> Option<Vec<f64>> {
+        self.0.objective_values()
+    }
+}
+
+/// Compare two fitness values, the feasible one first, and then the smaller
+/// evaluation value.
+pub(crate) fn cmp_fit<Y: Fitness>(a: &Y, b: &Y) -> core::cmp::Ordering {
+    let ord = b.is_feasible().cmp(&a.is_feasible());
+    ord.then_with(|| cmp_eval(&a.eval(), &b.eval()))
+}
+
+/// A multi-objective fitness type that exposes its objective values.
+///
+/// ```
+/// use metaheuristics_nature::MultiObjective;
+///
+/// #[derive(Clone)]
+/// struct MyFit {
+///     cost: f64,
+///     weight: f64,
+/// }
+///
+/// impl MultiObjective for MyFit {
+///     fn objectives(&self) -> Vec<f64> {
+///         vec![self.cost, self.weight]
+///     }
+/// }
+/// ```
+pub trait MultiObjective {
+    /// Get the objective values.
+    fn objectives(&self) -> Vec<f64>;
+}
+
+impl<Y: MultiObjective, P: ?Sized> MultiObjective for WithProduct<Y, P> {
+    fn objectives(&self) -> Vec<f64> {
+        self.ys.objectives()
+    }
+}
+
+/// A fitness type that can be averaged, used by [`Resample`] for the noisy
+/// objective functions.
+///
+/// For [`WithProduct`], only the fitness value is averaged, and the product of
+/// the first sample is kept.
+pub trait Average: Fitness {
+    /// Average the fitness values, the slice is not empty.
+    fn average(ys: &[Self]) -> Self;
+}
+
+macro_rules! impl_average {
+    ($($ty:ty),+) => {$(
+        impl Average for $ty {
+            fn average(ys: &[Self]) -> Self {
+                ys.iter().sum::<$ty>() / ys.len() as $ty
+            }
+        }
+    )+};
+}
+
+impl_average!(f32, f64);
+
+impl<Y: Average, P> Average for WithProduct<Y, P>
+where
+    P: MaybeParallel + Clone + 'static,
+{
+    fn average(ys: &[Self]) -> Self {
+        let avg = Y::average(&ys.iter().map(|p| p.ys.clone()).collect::<Vec<_>>());
+        Self::new_from_arc(avg, ys[0].product.clone())
+    }
+}
+
+/// A [`Fitness`] type that scalarizes the multi-objective fitness value with
+/// the weighted sum of its [`MultiObjective::objectives()`].
+///
+/// This wrapper type is overrided [`Fitness::Best`] to [`SingleBest`], and the
+/// evaluation value is the dot product of the objectives and the weights.
+/// This is a classic way to turn a multi-objective problem into a single
+/// objective problem with a steerable preference.
+///
+/// The weights can be shared with `Arc<RwLock<Vec<f64>>>` (requires `std`),
+/// so they can be changed during the run, such as in the
+/// [`SolverBuilder::callback()`]. The evaluation values are computed on
+/// demand and never cached, so the best container always compares with the
+/// current weights.
+#[derive(Clone, Debug)]
+pub struct WeightedSum<Y: Fitness + MultiObjective, W: Weights = Vec<f64>>(pub Y, pub W);
+
+impl<Y: Fitness + MultiObjective, W: Weights> Fitness for WeightedSum<Y, W> {
+    type Best<T: Fitness> = SingleBest<T>;
+    type Eval = f64;
+    fn is_dominated(&self, rhs: &Self) -> bool {
+        self.eval().is_dominated(&rhs.eval())
+    }
+    fn is_feasible(&self) -> bool {
+        self.0.is_feasible()
+    }
+    fn eval(&self) -> Self::Eval {
+        let ys = self.0.objectives();
+        self.1.with_weights(|w| {
+            debug_assert_eq!(ys.len(), w.len(), "Weights dimension mismatched");
+            core::iter::zip(ys, w).map(|(y, w)| y * w).sum()
+        })
+    }
+    fn objective_values(&self) -> Option<Vec<f64>> {
+        Some(self.0.objectives())
+    }
+}
+
+/// The weights container of [`WeightedSum`].
+pub trait Weights: MaybeParallel + Clone + 'static {
+    /// Access the weights.
+    fn with_weights<R>(&self, f: impl FnOnce(&[f64]) -> R) -> R;
+}
+
+impl Weights for Vec<f64> {
+    fn with_weights<R>(&self, f: impl FnOnce(&[f64]) -> R) -> R {
+        f(self)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Weights for Arc<std::sync::RwLock<Vec<f64>>> {
+    fn with_weights<R>(&self, f: impl FnOnce(&[f64]) -> R) -> R {
+        // The weights are always valid even if a thread is panicked
+        f(&self.read().unwrap_or_else(|e| e.into_inner()))
+    }
+}
+
+impl MultiObjective for f64 {
+    fn objectives(&self) -> Vec<f64> {
+        alloc::vec![*self]
+    }
+}
+
+impl MultiObjective for f32 {
+    fn objectives(&self) -> Vec<f64> {
+        alloc::vec![*self as f64]
+    }
+}
+
+/// A [`Fitness`] type that ignores the improvements smaller than the epsilon.
+///
+/// The value `a` dominates `b` only if `a` dominates `b` with the inner
+/// fitness type, and at least one of its [`MultiObjective::objectives()`] is
+/// better by more than the epsilon. For a single objective, it means
+/// `a + eps < b`.
+///
+/// The tiny floating-point differences no longer update the best solution, so
+/// the convergence curve is stable. The [`Fitness::Best`] type is inherited.
+/// Use [`SolverBuilder::eps_dominance()`] to apply the same comparison without
+/// wrapping the fitness type.
+///
+/// ```
+/// use metaheuristics_nature::{EpsDominance, Fitness};
+///
+/// let (a, b) = (EpsDominance(1., 1e-6), EpsDominance(1. + 1e-9, 1e-6));
+/// assert!(!a.is_dominated(&b));
+/// let b = EpsDominance(2., 1e-6);
+/// assert!(a.is_dominated(&b));
+/// ```
+#[derive(Clone, Debug)]
+pub struct EpsDominance<Y: Fitness + MultiObjective>(pub Y, pub f64);
+
+impl<Y: Fitness + MultiObjective> Fitness for EpsDominance<Y> {
+    type Best<T: Fitness> = Y::Best<T>;
+    type Eval = Y::Eval;
+    fn is_dominated(&self, rhs: &Self) -> bool {
+        self.0.is_dominated(&rhs.0) && eps_better(&self.0.objectives(), &rhs.0.objectives(), self.1)
+    }
+    fn is_feasible(&self) -> bool {
+        self.0.is_feasible()
+    }
+    fn eval(&self) -> Self::Eval {
+        self.0.eval()
+    }
+    fn objective_values(&self) -> Option<Vec<f64>> {
+        Some(self.0.objectives())
+    }
+}
+
+impl<Y: Fitness + MultiObjective> MultiObjective for EpsDominance<Y> {
+    fn objectives(&self) -> Vec<f64> {
+        self.0.objectives()
+    }
+}
+
+// Check if any objective of `a` is better than `b` by more than the epsilon
+pub(crate) fn eps_better(a: &[f64], b: &[f64], eps: f64) -> bool {
+    core::iter::zip(a, b).any(|(a, b)| a + eps < *b)
+}
+
+/// A [`Fitness`] type with the total violation of the constraints, see
+/// [`Constrained`].
+///
+/// The values are compared with Deb's feasibility rules:
+///
+/// 1. A feasible solution dominates an infeasible solution.
+/// 1. Between two feasible solutions, compare them with the inner fitness.
+/// 1. Between two infeasible solutions, the smaller violation wins.
+///
+/// The [`Fitness::Best`] type is inherited.
+#[derive(Clone, Debug)]
+pub struct ConstrainedFit<Y: Fitness> {
+    /// The inner fitness value
+    pub ys: Y,
+    /// The total violation, zero if feasible
+    pub violation: f64,
+}
+
+impl<Y: Fitness> Fitness for ConstrainedFit<Y> {
+    type Best<T: Fitness> = Y::Best<T>;
+    type Eval = Y::Eval;
+    fn is_dominated(&self, rhs: &Self) -> bool {
+        match (self.is_feasible(), rhs.is_feasible()) {
+            (true, true) => self.ys.is_dominated(&rhs.ys),
+            (false, false) => self.violation < rhs.violation,
+            (feasible, _) => feasible,
+        }
+    }
+    fn is_feasible(&self) -> bool {
+        self.violation <= 0. && self.ys.is_feasible()
+    }
+    fn eval(&self) -> Self::Eval {
+        self.ys.eval()
+    }
+    fn objective_values(&self) -> Option<Vec<f64>> {
+        self.ys.objective_values()
+    }
+}
+
+impl<Y: Fitness + MultiObjective> MultiObjective for ConstrainedFit<Y> {
+    fn objectives(&self) -> Vec<f64> {
+        self.ys.objectives()
+    }
 }
 
 /// A [`Fitness`] type carrying final results.
@@ -155,7 +490,13 @@ where
     fn is_dominated(&self, rhs: &Self) -> bool {
         self.ys.is_dominated(&rhs.ys)
     }
+    fn is_feasible(&self) -> bool {
+        self.ys.is_feasible()
+    }
     fn eval(&self) -> Self::Eval {
         self.ys.eval()
     }
+    fn objective_values(&self) -> Option<Vec<f64>> {
+        self.ys.objective_values()
+    }
 }
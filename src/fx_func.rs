@@ -1,5 +1,6 @@
 use crate::prelude::*;
-use alloc::boxed::Box;
+use alloc::{boxed::Box, vec::Vec};
+use core::iter::zip;
 
 /// A quick interface help to create objective function from a callable object.
 ///
@@ -13,7 +14,8 @@ use alloc::boxed::Box;
 /// let s = Solver::build(Rga::default(), f)
 ///     .seed(0)
 ///     .task(|ctx| ctx.gen == 20)
-///     .solve();
+///     .solve()
+///     .unwrap();
 /// ```
 pub struct Fx<'b, 'f, Y: Fitness, const DIM: usize> {
     bound: &'b [[f64; 2]; DIM],
@@ -44,3 +46,625 @@ impl<Y: Fitness, const DIM: usize> ObjFunc for Fx<'_, '_, Y, DIM> {
         (self.func)(xs.try_into().unwrap_or_else(|_| unreachable!()))
     }
 }
+
+/// A quick interface help to create objective function from a callable object
+/// with runtime-determined dimension.
+///
+/// See also [`Fx`] for the constant dimension.
+///
+/// ```
+/// use metaheuristics_nature::{FxDyn, Rga, Solver};
+///
+/// # let dim = 4;
+/// let bound = vec![[-50., 50.]; dim];
+/// let f = FxDyn::new(bound, |xs| xs.iter().map(|x| x * x).sum::<f64>());
+/// let s = Solver::build(Rga::default(), f)
+///     .seed(0)
+///     .task(|ctx| ctx.gen == 20)
+///     .solve()
+///     .unwrap();
+/// ```
+pub struct FxDyn<'f, Y: Fitness> {
+    bound: Vec<[f64; 2]>,
+    #[allow(clippy::type_complexity)]
+    func: Box<dyn Fn(&[f64]) -> Y + Sync + Send + 'f>,
+}
+
+impl<'f, Y: Fitness> FxDyn<'f, Y> {
+    /// Create objective function from a callable object.
+    pub fn new<F>(bound: Vec<[f64; 2]>, func: F) -> Self
+    where
+        F: Fn(&[f64]) -> Y + Sync + Send + 'f,
+    {
+        Self { func: Box::new(func), bound }
+    }
+}
+
+impl<Y: Fitness> Bounded for FxDyn<'_, Y> {
+    #[inline]
+    fn bound(&self) -> &[[f64; 2]] {
+        &self.bound
+    }
+}
+
+impl<Y: Fitness> ObjFunc for FxDyn<'_, Y> {
+    type Ys = Y;
+    fn fitness(&self, xs: &[f64]) -> Self::Ys {
+        (self.func)(xs)
+    }
+}
+
+/// An objective function wrapper that searches some variables in log scale.
+///
+/// The algorithm sees the natural logarithm of the masked bounds, and the
+/// variables are exponentiated before calling the inner objective function.
+/// This is useful for the variables that span several orders of magnitude.
+///
+/// The design variables of the solver result are in log scale, use
+/// [`LogScale::to_original()`] to convert them back.
+///
+/// ```
+/// use metaheuristics_nature::{Fx, LogScale, Rga, Solver};
+///
+/// let bound = [[1e-6, 1e2], [-50., 50.]];
+/// let f = Fx::new(&bound, |&[a, b]| (a.log10() + 3.).powi(2) + b * b);
+/// let f = LogScale::new(f, &[true, false]);
+/// let s = Solver::build(Rga::default(), f)
+///     .seed(0)
+///     .task(|ctx| ctx.gen == 20)
+///     .solve()
+///     .unwrap();
+/// let xs = s.func().to_original(s.as_best_xs());
+/// ```
+pub struct LogScale<'a, F: ObjFunc> {
+    func: F,
+    mask: &'a [bool],
+    bound: Vec<[f64; 2]>,
+}
+
+impl<'a, F: ObjFunc> LogScale<'a, F> {
+    /// Wrap the objective function, the variables are log-scaled if their
+    /// `mask` is true.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of `mask` is not the same as the dimension, or the
+    /// lower bound of a log-scaled variable is not positive.
+    pub fn new(func: F, mask: &'a [bool]) -> Self {
+        assert_eq!(func.dim(), mask.len(), "Mask dimension mismatched");
+        let bound = zip(func.bounds_iter(), mask)
+            .map(|([lb, ub], &log)| {
+                if log {
+                    assert!(lb > 0., "Log-scaled lower bound should be positive");
+                    [lb.ln(), ub.ln()]
+                } else {
+                    [lb, ub]
+                }
+            })
+            .collect();
+        Self { func, mask, bound }
+    }
+
+    /// Get the inner objective function.
+    pub fn inner(&self) -> &F {
+        &self.func
+    }
+
+    /// Convert the design variables back to the original scale.
+    pub fn to_original(&self, xs: &[f64]) -> Vec<f64> {
+        zip(xs, self.mask)
+            .map(|(&x, &log)| if log { x.exp() } else { x })
+            .collect()
+    }
+}
+
+impl<F: ObjFunc> Bounded for LogScale<'_, F> {
+    #[inline]
+    fn bound(&self) -> &[[f64; 2]] {
+        &self.bound
+    }
+}
+
+impl<F: ObjFunc> ObjFunc for LogScale<'_, F> {
+    type Ys = F::Ys;
+    fn fitness(&self, xs: &[f64]) -> Self::Ys {
+        self.func.fitness(&self.to_original(xs))
+    }
+
+    fn fitness_ctx(
+        &self,
+        xs: &[f64],
+        progress: &Progress<<Self::Ys as Fitness>::Eval>,
+    ) -> Self::Ys {
+        self.func.fitness_ctx(&self.to_original(xs), progress)
+    }
+
+    fn fitness_batch(
+        &self,
+        pool: &[Vec<f64>],
+        progress: &Progress<<Self::Ys as Fitness>::Eval>,
+    ) -> Vec<Self::Ys> {
+        let pool = pool
+            .iter()
+            .map(|xs| self.to_original(xs))
+            .collect::<Vec<_>>();
+        self.func.fitness_batch(&pool, progress)
+    }
+}
+
+/// An objective function wrapper that holds some variables at fixed values.
+///
+/// The solver only searches the free variables (the `None` items of `fixed`),
+/// and the full design variables are reconstructed before calling the inner
+/// objective function. This is useful for the staged optimization.
+///
+/// The design variables of the solver result are reduced, use
+/// [`Freeze::to_full()`] to reconstruct them.
+///
+/// ```
+/// use metaheuristics_nature::{Freeze, Fx, Rga, Solver};
+///
+/// let bound = [[-50., 50.]; 3];
+/// let f = Fx::new(&bound, |&[a, b, c]| a * a + b * b + c * c);
+/// let f = Freeze::new(f, &[None, Some(1.), None]);
+/// let s = Solver::build(Rga::default(), f)
+///     .seed(0)
+///     .task(|ctx| ctx.gen == 20)
+///     .solve()
+///     .unwrap();
+/// let xs = s.func().to_full(s.as_best_xs());
+/// assert_eq!(xs.len(), 3);
+/// assert_eq!(xs[1], 1.);
+/// ```
+pub struct Freeze<'a, F: ObjFunc> {
+    func: F,
+    fixed: &'a [Option<f64>],
+    free: Vec<usize>,
+    bound: Vec<[f64; 2]>,
+}
+
+impl<'a, F: ObjFunc> Freeze<'a, F> {
+    /// Wrap the objective function, the variables are fixed if their items
+    /// of `fixed` are not none.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of `fixed` is not the same as the dimension.
+    pub fn new(func: F, fixed: &'a [Option<f64>]) -> Self {
+        assert_eq!(func.dim(), fixed.len(), "Fixed dimension mismatched");
+        let free = (0..fixed.len())
+            .filter(|&s| fixed[s].is_none())
+            .collect::<Vec<_>>();
+        let bound = free.iter().map(|&s| func.bound_of(s)).collect();
+        Self { func, fixed, free, bound }
+    }
+
+    /// Get the inner objective function.
+    pub fn inner(&self) -> &F {
+        &self.func
+    }
+
+    /// Reconstruct the full design variables from the free variables.
+    pub fn to_full(&self, xs: &[f64]) -> Vec<f64> {
+        let mut xs = xs.iter();
+        self.fixed
+            .iter()
+            .map(|v| v.unwrap_or_else(|| *xs.next().expect("Dimension mismatched")))
+            .collect()
+    }
+}
+
+impl<F: ObjFunc> Bounded for Freeze<'_, F> {
+    #[inline]
+    fn bound(&self) -> &[[f64; 2]] {
+        &self.bound
+    }
+
+    #[inline]
+    fn granularity(&self, s: usize) -> Option<f64> {
+        self.func.granularity(self.free[s])
+    }
+}
+
+impl<F: ObjFunc> ObjFunc for Freeze<'_, F> {
+    type Ys = F::Ys;
+    fn fitness(&self, xs: &[f64]) -> Self::Ys {
+        self.func.fitness(&self.to_full(xs))
+    }
+
+    fn fitness_ctx(
+        &self,
+        xs: &[f64],
+        progress: &Progress<<Self::Ys as Fitness>::Eval>,
+    ) -> Self::Ys {
+        self.func.fitness_ctx(&self.to_full(xs), progress)
+    }
+
+    fn fitness_batch(
+        &self,
+        pool: &[Vec<f64>],
+        progress: &Progress<<Self::Ys as Fitness>::Eval>,
+    ) -> Vec<Self::Ys> {
+        let pool = pool.iter().map(|xs| self.to_full(xs)).collect::<Vec<_>>();
+        self.func.fitness_batch(&pool, progress)
+    }
+}
+
+/// An objective function wrapper that handles the inequality constraints
+/// `g(x) <= 0` separately from the objective.
+///
+/// The fitness value is wrapped by [`ConstrainedFit`], which is compared with
+/// Deb's feasibility rules instead of the penalty weights.
+///
+/// ```
+/// use metaheuristics_nature::{Constrained, Fitness, Fx, Rga, Solver};
+///
+/// let bound = [[-50., 50.]; 2];
+/// let f = Fx::new(&bound, |&[a, b]| a * a + b * b);
+/// // Subject to `a + b >= 10`
+/// let f = Constrained::new(f, |xs: &[f64]| vec![10. - xs[0] - xs[1]]);
+/// let s = Solver::build(Rga::default(), f)
+///     .seed(0)
+///     .task(|ctx| ctx.gen == 50)
+///     .solve()
+///     .unwrap();
+/// assert!(s.as_best_fit().is_feasible());
+/// ```
+pub struct Constrained<F: ObjFunc, C> {
+    func: F,
+    constraints: C,
+}
+
+impl<F, C> Constrained<F, C>
+where
+    F: ObjFunc,
+    C: Fn(&[f64]) -> Vec<f64> + MaybeParallel,
+{
+    /// Wrap the objective function with the `constraints` function, which
+    /// returns the values of `g_i(x)` to be non-positive.
+    pub fn new(func: F, constraints: C) -> Self {
+        Self { func, constraints }
+    }
+
+    /// Get the inner objective function.
+    pub fn inner(&self) -> &F {
+        &self.func
+    }
+
+    /// Get the total violation of the constraints, zero if feasible.
+    pub fn violation(&self, xs: &[f64]) -> f64 {
+        (self.constraints)(xs).into_iter().map(|g| g.max(0.)).sum()
+    }
+}
+
+impl<F, C> Bounded for Constrained<F, C>
+where
+    F: ObjFunc,
+    C: Fn(&[f64]) -> Vec<f64> + MaybeParallel,
+{
+    #[inline]
+    fn bound(&self) -> &[[f64; 2]] {
+        self.func.bound()
+    }
+
+    #[inline]
+    fn granularity(&self, s: usize) -> Option<f64> {
+        self.func.granularity(s)
+    }
+}
+
+impl<F, C> ObjFunc for Constrained<F, C>
+where
+    F: ObjFunc,
+    C: Fn(&[f64]) -> Vec<f64> + MaybeParallel,
+{
+    type Ys = ConstrainedFit<F::Ys>;
+    fn fitness(&self, xs: &[f64]) -> Self::Ys {
+        ConstrainedFit {
+            ys: self.func.fitness(xs),
+            violation: self.violation(xs),
+        }
+    }
+
+    fn fitness_ctx(
+        &self,
+        xs: &[f64],
+        progress: &Progress<<Self::Ys as Fitness>::Eval>,
+    ) -> Self::Ys {
+        let ys = self.func.fitness_ctx(xs, progress);
+        ConstrainedFit { ys, violation: self.violation(xs) }
+    }
+
+    fn fitness_batch(
+        &self,
+        pool: &[Vec<f64>],
+        progress: &Progress<<Self::Ys as Fitness>::Eval>,
+    ) -> Vec<Self::Ys> {
+        zip(self.func.fitness_batch(pool, progress), pool)
+            .map(|(ys, xs)| ConstrainedFit { ys, violation: self.violation(xs) })
+            .collect()
+    }
+}
+
+/// An objective function wrapper that evaluates a noisy objective function
+/// several times and averages the fitness values.
+///
+/// This is the standard way to optimize under noise, the selection is less
+/// likely misled by a lucky evaluation. The fitness type should implement
+/// [`Average`]. The evaluation counter [`Ctx::eval_count()`] counts each
+/// averaged design once. See also [`SolverBuilder::resample()`], which keeps
+/// the objective function type.
+///
+/// ```
+/// use metaheuristics_nature::{Fx, Resample, Rga, Solver};
+/// use std::sync::atomic::{AtomicBool, Ordering};
+///
+/// let bound = [[-50., 50.]; 2];
+/// let flip = AtomicBool::new(false);
+/// let f = Fx::new(&bound, |&[a, b]| {
+///     // A fake noise
+///     let noise = if flip.fetch_xor(true, Ordering::Relaxed) { -1. } else { 1. };
+///     a * a + b * b + 10. + noise
+/// });
+/// let s = Solver::build(Rga::default(), Resample::new(f, 4))
+///     .seed(0)
+///     .task(|ctx| ctx.gen == 20)
+///     .solve()
+///     .unwrap();
+/// ```
+pub struct Resample<F: ObjFunc> {
+    func: F,
+    n: usize,
+}
+
+impl<F: ObjFunc> Resample<F>
+where
+    F::Ys: Average,
+{
+    /// Wrap the objective function, which is evaluated `n` times for each
+    /// design.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero.
+    pub fn new(func: F, n: usize) -> Self {
+        assert!(n > 0, "The number of samples should be greater than 0");
+        Self { func, n }
+    }
+
+    /// Get the inner objective function.
+    pub fn inner(&self) -> &F {
+        &self.func
+    }
+}
+
+impl<F: ObjFunc> Bounded for Resample<F> {
+    #[inline]
+    fn bound(&self) -> &[[f64; 2]] {
+        self.func.bound()
+    }
+
+    #[inline]
+    fn granularity(&self, s: usize) -> Option<f64> {
+        self.func.granularity(s)
+    }
+}
+
+impl<F: ObjFunc> ObjFunc for Resample<F>
+where
+    F::Ys: Average,
+{
+    type Ys = F::Ys;
+    fn fitness(&self, xs: &[f64]) -> Self::Ys {
+        let ys = (0..self.n)
+            .map(|_| self.func.fitness(xs))
+            .collect::<Vec<_>>();
+        Average::average(&ys)
+    }
+
+    fn fitness_ctx(
+        &self,
+        xs: &[f64],
+        progress: &Progress<<Self::Ys as Fitness>::Eval>,
+    ) -> Self::Ys {
+        resample_fitness(&self.func, xs, progress, self.n, Average::average)
+    }
+
+    fn fitness_batch(
+        &self,
+        pool: &[Vec<f64>],
+        progress: &Progress<<Self::Ys as Fitness>::Eval>,
+    ) -> Vec<Self::Ys> {
+        resample_batch(&self.func, pool, progress, self.n, Average::average)
+    }
+}
+
+// Evaluate the design `n` times and average the fitness values, also used by
+// `SolverBuilder::resample()`
+pub(crate) fn resample_fitness<F: ObjFunc>(
+    func: &F,
+    xs: &[f64],
+    progress: &Progress<<F::Ys as Fitness>::Eval>,
+    n: usize,
+    average: fn(&[F::Ys]) -> F::Ys,
+) -> F::Ys {
+    let ys = (0..n)
+        .map(|_| func.fitness_ctx(xs, progress))
+        .collect::<Vec<_>>();
+    average(&ys)
+}
+
+pub(crate) fn resample_batch<F: ObjFunc>(
+    func: &F,
+    pool: &[Vec<f64>],
+    progress: &Progress<<F::Ys as Fitness>::Eval>,
+    n: usize,
+    average: fn(&[F::Ys]) -> F::Ys,
+) -> Vec<F::Ys> {
+    // Evaluate the samples in one batch
+    let pool = (pool.iter())
+        .flat_map(|xs| core::iter::repeat_n(xs.clone(), n))
+        .collect::<Vec<_>>();
+    (func.fitness_batch(&pool, progress).chunks(n))
+        .map(average)
+        .collect()
+}
+
+/// An objective function wrapper that memoizes the fitness values.
+///
+/// The design variables are quantized by the `resolution`, and the fitness
+/// value is reused if the quantized variables are evaluated before. This is
+/// useful when the algorithm re-evaluates identical or near-identical designs,
+/// such as the elites and the duplicated individuals.
+///
+/// The cache is protected by a [`std::sync::Mutex`], which is an example of
+/// the interior mutability for the objective function. The lock is not held
+/// during the evaluation, so the same design may be evaluated more than once in
+/// parallel.
+///
+/// The cached value is returned by [`ObjFunc::fitness()`], so the
+/// [`ObjFunc::fitness_ctx()`] of the inner function is not used.
+///
+/// ```
+/// use metaheuristics_nature::{CachedObjFunc, Fx, Rga, Solver};
+///
+/// let bound = [[-50., 50.]; 2];
+/// let f = Fx::new(&bound, |&[a, b]| a * a + b * b);
+/// let f = CachedObjFunc::new(f, 1e-9);
+/// let s = Solver::build(Rga::default(), f)
+///     .seed(0)
+///     .task(|ctx| ctx.gen == 20)
+///     .solve()
+///     .unwrap();
+/// assert!(s.func().cache_len() > 0);
+/// ```
+#[cfg(feature = "std")]
+pub struct CachedObjFunc<F: ObjFunc> {
+    func: F,
+    resolution: f64,
+    cache: std::sync::Mutex<std::collections::HashMap<Vec<i64>, F::Ys>>,
+}
+
+#[cfg(feature = "std")]
+impl<F: ObjFunc> CachedObjFunc<F> {
+    /// Wrap the objective function with the quantization `resolution`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the resolution is not positive.
+    pub fn new(func: F, resolution: f64) -> Self {
+        assert!(resolution > 0., "Resolution should be positive");
+        Self { func, resolution, cache: Default::default() }
+    }
+
+    /// Get the inner objective function.
+    pub fn inner(&self) -> &F {
+        &self.func
+    }
+
+    /// Get the number of cached fitness values.
+    pub fn cache_len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Remove all cached fitness values.
+    pub fn clear_cache(&self) {
+        self.lock().clear();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, std::collections::HashMap<Vec<i64>, F::Ys>> {
+        // The cache is always valid even if a thread is panicked
+        self.cache.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<F: ObjFunc> Bounded for CachedObjFunc<F> {
+    #[inline]
+    fn bound(&self) -> &[[f64; 2]] {
+        self.func.bound()
+    }
+
+    #[inline]
+    fn granularity(&self, s: usize) -> Option<f64> {
+        self.func.granularity(s)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<F: ObjFunc> ObjFunc for CachedObjFunc<F> {
+    type Ys = F::Ys;
+    fn fitness(&self, xs: &[f64]) -> Self::Ys {
+        let key = xs
+            .iter()
+            .map(|x| (x / self.resolution).round() as i64)
+            .collect::<Vec<_>>();
+        if let Some(ys) = self.lock().get(&key) {
+            return ys.clone();
+        }
+        let ys = self.func.fitness(xs);
+        self.lock().insert(key, ys.clone());
+        ys
+    }
+}
+
+/// A surrogate model of the objective function, used by
+/// [`SolverBuilder::surrogate()`].
+///
+/// The model is trained by the true evaluations, and predicts the fitness value
+/// of the unevaluated designs.
+pub trait Surrogate<Y: Fitness>: MaybeParallel {
+    /// Predict the fitness value of the design variables.
+    ///
+    /// Return none if the model is not ready, then the true objective function
+    /// will be used.
+    fn predict(&self, xs: &[f64]) -> Option<Y>;
+    /// Train the model with a true evaluation.
+    fn update(&mut self, xs: &[f64], ys: &Y);
+}
+
+/// A nearest neighbor surrogate model, which predicts the fitness value of the
+/// closest evaluated design.
+///
+/// This is a simple reference model of the [`Surrogate`] trait, which works
+/// with any fitness type. The prediction is linear to the number of samples.
+#[derive(Clone, Debug)]
+pub struct NearestSurrogate<Y> {
+    samples: Vec<(Vec<f64>, Y)>,
+    min_samples: usize,
+}
+
+impl<Y> NearestSurrogate<Y> {
+    /// Create an empty model, which is ready after `min_samples` samples are
+    /// collected.
+    pub fn new(min_samples: usize) -> Self {
+        Self { samples: Vec::new(), min_samples }
+    }
+
+    /// Get the number of the collected samples.
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    /// Check if the model has no sample.
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+}
+
+impl<Y: Fitness> Surrogate<Y> for NearestSurrogate<Y> {
+    fn predict(&self, xs: &[f64]) -> Option<Y> {
+        if self.samples.len() < self.min_samples {
+            return None;
+        }
+        let dist = |v: &[f64]| zip(xs, v).map(|(a, b)| (a - b) * (a - b)).sum::<f64>();
+        (self.samples.iter())
+            .map(|(v, ys)| (dist(v), ys))
+            .min_by(|(a, _), (b, _)| a.total_cmp(b))
+            .map(|(_, ys)| ys.clone())
+    }
+
+    fn update(&mut self, xs: &[f64], ys: &Y) {
+        self.samples.push((xs.to_vec(), ys.clone()));
+    }
+}
@@ -0,0 +1,230 @@
+use crate::prelude::*;
+use alloc::{boxed::Box, vec::Vec};
+use core::iter::zip;
+
+/// An island model solver, created by [`Solver::build_islands()`].
+///
+/// Each island is a sub-population with its own algorithm, they can be
+/// different methods. Every `migrate_every` generations, the best
+/// `migrate_count` individuals of each island replace the worst individuals of
+/// the next island, where the islands are connected as a ring. The islands are
+/// run in parallel if the `rayon` feature is enabled.
+///
+/// The solver of the island with the best result is returned. The evaluation
+/// budget is the population number times the number of islands and the
+/// generations.
+///
+/// ```
+/// use metaheuristics_nature::{AlgCfg, De, Pso, Rga, Solver};
+/// # use metaheuristics_nature::tests::TestObj as MyFunc;
+///
+/// let islands = [De::default().boxed(), Rga::default().boxed()];
+/// let s = Solver::build_islands(islands, MyFunc::new())
+///     .island(Pso::default())
+///     .pop_num(20)
+///     .migrate_every(10)
+///     .migrate_count(2)
+///     .seed(0)
+///     .task_gen(50)
+///     .solve()
+///     .unwrap();
+/// ```
+#[must_use = "solver builder do nothing unless call the \"solve\" method"]
+pub struct IslandSolver<F: ObjFunc> {
+    func: F,
+    islands: Vec<maybe_send_box!(Algorithm<F>)>,
+    pop_num: usize,
+    seed: SeedOpt,
+    max_gen: u64,
+    migrate_every: u64,
+    migrate_count: usize,
+    report: Option<fn(&Ctx<F>) -> f64>,
+}
+
+impl<F: ObjFunc> IslandSolver<F> {
+    pub(crate) fn new(islands: Vec<maybe_send_box!(Algorithm<F>)>, func: F) -> Self {
+        Self {
+            func,
+            islands,
+            pop_num: 200,
+            seed: SeedOpt::default(),
+            max_gen: 200,
+            migrate_every: 10,
+            migrate_count: 1,
+            report: None,
+        }
+    }
+
+    /// Append an island with the algorithm setting.
+    ///
+    /// The default population number of each island is decided by the first
+    /// island if it is appended by this method.
+    pub fn island<A: AlgCfg>(mut self, cfg: A) -> Self {
+        if self.islands.is_empty() {
+            self.pop_num = A::pop_num();
+        }
+        self.islands.push(cfg.boxed());
+        self
+    }
+
+    impl_builders! {
+        /// Population number of each island.
+        ///
+        /// # Default
+        ///
+        /// By default, the population number is decided by the first island
+        /// appended by [`IslandSolver::island()`], otherwise 200.
+        fn pop_num(usize)
+        /// Migration interval in generations.
+        ///
+        /// # Default
+        ///
+        /// By default, the individuals are migrated every 10 generations.
+        fn migrate_every(u64)
+        /// Number of the migrated individuals of each island.
+        ///
+        /// # Default
+        ///
+        /// By default, only the best individual is migrated.
+        fn migrate_count(usize)
+    }
+
+    /// Set the random seed, see [`SolverBuilder::seed()`].
+    ///
+    /// The seeds of the islands are derived from this seed.
+    pub fn seed(self, seed: impl Into<SeedOpt>) -> Self {
+        Self { seed: seed.into(), ..self }
+    }
+
+    /// Termination condition by the generation number.
+    ///
+    /// # Default
+    ///
+    /// By default, the algorithm will iterate 200 generation.
+    pub fn task_gen(self, max_gen: u64) -> Self {
+        Self { max_gen, ..self }
+    }
+
+    /// Record the best evaluation value of all islands in each generation,
+    /// see [`SolverBuilder::track_reports()`].
+    ///
+    /// # Default
+    ///
+    /// By default, the reports are not recorded.
+    pub fn track_reports(self) -> Self
+    where
+        <F::Ys as Fitness>::Eval: Into<f64>,
+    {
+        Self {
+            report: Some(|ctx| ctx.best.get_eval().into()),
+            ..self
+        }
+    }
+
+    /// Run the islands, and return the solver with the best result.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`SolverBuilder::solve()`], or
+    /// [`SolverError::NoIsland`] and [`SolverError::ZeroMigrationInterval`].
+    pub fn solve(self) -> Result<Solver<F>, SolverError>
+    where
+        F: Clone,
+    {
+        let Self {
+            func,
+            islands,
+            pop_num,
+            seed,
+            max_gen,
+            migrate_every,
+            migrate_count,
+            report,
+        } = self;
+        if islands.is_empty() {
+            return Err(SolverError::NoIsland);
+        }
+        if migrate_every == 0 {
+            return Err(SolverError::ZeroMigrationInterval);
+        }
+        let pool = Pool::<F>::Func(Box::new(uniform_pool()));
+        pool.check(&func)?;
+        let mut rng = Rng::new(seed);
+        let mut islands = (islands.into_iter())
+            .map(|mut algorithm| {
+                let mut rng = Rng::new(SeedOpt::Seed(rng.gen()));
+                let mut ctx = pool.build_ctx(func.clone(), pop_num, usize::MAX, None, &mut rng)?;
+                ctx.max_gen = Some(max_gen);
+                algorithm.init(&mut ctx, &mut rng);
+                // The best evaluation value of each generation
+                let history = report.map(|f| alloc::vec![f(&ctx)]).unwrap_or_default();
+                Ok((ctx, rng, algorithm, history))
+            })
+            .collect::<Result<Vec<_>, SolverError>>()?;
+        let mut gen = 0;
+        while gen < max_gen {
+            let epoch = migrate_every.min(max_gen - gen);
+            gen += epoch;
+            #[cfg(not(feature = "rayon"))]
+            let iter = islands.iter_mut();
+            #[cfg(feature = "rayon")]
+            let iter = islands.par_iter_mut();
+            iter.for_each(|(ctx, rng, algorithm, history)| {
+                for _ in 0..epoch {
+                    ctx.gen += 1;
+                    ctx.update_progress();
+                    algorithm.generation(ctx, rng);
+                    if let Some(f) = report {
+                        history.push(f(ctx));
+                    }
+                }
+            });
+            if gen < max_gen && migrate_count > 0 {
+                migrate(&mut islands, migrate_count);
+            }
+        }
+        // The best of all islands in each generation
+        let mut reports = Vec::<GenReport>::new();
+        if report.is_some() {
+            for (i, gen) in (0..=max_gen).enumerate() {
+                let best_eval = (islands.iter())
+                    .map(|(.., history)| history[i])
+                    .fold(f64::INFINITY, f64::min);
+                let diff = reports.last().map_or(0., |r| r.best_eval - best_eval);
+                reports.push(GenReport { gen, best_eval, diff });
+            }
+        }
+        let (ctx, rng, ..) = (islands.into_iter())
+            .reduce(|best, island| {
+                if cmp_eval(&island.0.best.get_eval(), &best.0.best.get_eval()).is_lt() {
+                    island
+                } else {
+                    best
+                }
+            })
+            .unwrap();
+        Ok(Solver::new(ctx, rng, reports, Vec::new()))
+    }
+}
+
+// Replace the worst individuals of each island with the best individuals of
+// the previous island on the ring
+fn migrate<F: ObjFunc, T, H>(islands: &mut [(Ctx<F>, Rng, T, H)], n: usize) {
+    let emigrants = (islands.iter())
+        .map(|(ctx, ..)| {
+            (ctx.best_indices(n).into_iter())
+                .map(|i| (ctx.pool[i].clone(), ctx.pool_y[i].clone()))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    let k = islands.len();
+    for (i, migrants) in emigrants.into_iter().enumerate() {
+        let ctx = &mut islands[(i + 1) % k].0;
+        let worst = ctx.rank().into_iter().rev().take(migrants.len());
+        let worst = worst.collect::<Vec<_>>();
+        for (j, (xs, ys)) in zip(worst, migrants) {
+            ctx.set_from(j, xs, ys);
+        }
+        ctx.find_best();
+    }
+}
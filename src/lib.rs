@@ -17,35 +17,53 @@
 //! the result. This type allows you to use the pre-defined methods without
 //! importing any traits.
 //!
-//! All provided methods are listed in the module [`methods`].
+//! All provided methods are listed in the module [`methods`]. They can be
+//! chained in phases with [`Sequential`], or run as parallel sub-populations
+//! with [`IslandSolver`].
 //!
 //! For making your owned method, please see [`prelude`].
 //!
 //! # Objective Function
 //!
-//! For a quick demo with callable object, please see [`Fx`].
+//! For a quick demo with callable object, please see [`Fx`] and [`FxDyn`].
 //!
 //! You can define your question as an objective function through implementing
 //! [`ObjFunc`], and then the upper bound, lower bound, and an objective
 //! function [`ObjFunc::fitness()`] returns [`Fitness`] should be defined.
+//! The [`LogScale`] wrapper searches the variables in log scale, the [`Freeze`]
+//! wrapper holds some variables at fixed values, the [`Constrained`] wrapper
+//! handles the inequality constraints, the [`Resample`] wrapper averages a
+//! noisy objective function, and the `CachedObjFunc`
+//! wrapper memoizes the fitness values (requires `std`). A [`Surrogate`] model
+//! can replace some evaluations, see `SolverBuilder::surrogate()`.
 //!
 //! # Random Function
 //!
 //! This crate uses a 64bit ChaCha algorithm ([`random::Rng`]) to generate
-//! uniform random values. Before that, a random seed is required. The seed is
-//! generated by `getrandom` crate, please see its support platform.
+//! uniform random values. The backend is defined by the [`random::ChaCha`]
+//! alias in one place. Before that, a random seed is required. The seed is
+//! generated by `getrandom` crate, please see its support platform. Use the
+//! deterministic seed options (such as [`random::SeedOpt::Counter`]) on the
+//! platforms without `getrandom`.
 //!
 //! # Features
 //!
 //! The crate features:
 //! + `std`: Default feature. Enable standard library function, such as timing
 //!   and threading. If `std` is disabled, crate "libm" will be enabled for the
-//!   math functions.
+//!   math functions. This feature enables the `entropy` feature.
+//! + `entropy`: Generate the random seed by `getrandom` crate.
+//!   If disabled, [`random::SeedOpt::Entropy`] is not available and the
+//!   default seed is [`random::SeedOpt::Counter`].
 //! + `rayon`: Enable parallel computation via `rayon`. Disable it for the
 //!   platform that doesn't supported threading, or if your objective function
 //!   is not complicate enough. This feature require `std` feature.
 //! + `clap`: Add CLI argument support for the provided algorithms and their
 //!   options.
+//! + `derive`: Enable the derive macro [`MultiObjective`] for the
+//!   multi-objective fitness types.
+//! + `ndarray`: Add the conversions from and to the `ndarray` arrays, such as
+//!   `Pool::from_array()` and `Solver::best_xs_array()`.
 //!
 //! # Compatibility
 //!
@@ -56,20 +74,62 @@
 //! version number. Then re-export (`pub use`) this crate for the downstream
 //! crates.
 //!
-//! This crate does the same things on `rand` and `rayon`.
+//! This crate does the same things on `rand`, `rayon` and `ndarray`.
 #![cfg_attr(doc_cfg, feature(doc_auto_cfg))]
 #![cfg_attr(not(feature = "std"), no_std)]
 extern crate alloc;
 #[cfg(not(feature = "std"))]
 extern crate core as std;
+#[cfg(feature = "ndarray")]
+pub use ndarray;
 pub use rand;
 #[cfg(feature = "rayon")]
 pub use rayon;
 
 pub use self::{
-    algorithm::*, ctx::*, fitness::*, fx_func::*, methods::*, obj_func::*, solver::*,
-    solver_builder::*,
+    algorithm::*, ctx::*, fitness::*, fx_func::*, island::*, methods::*, obj_func::*, schedule::*,
+    solver::*, solver_builder::*,
 };
+/// Derive [`Fitness`] and [`MultiObjective`] for a multi-objective struct.
+///
+/// The fields marked with `#[objective]` are the objectives, which should be
+/// convertible to `f64` with `as` casting. The other fields are ignored.
+///
+/// + [`Fitness::Best`] is [`Pareto`](pareto::Pareto).
+/// + [`Fitness::is_dominated()`] is the Pareto dominance, where `a`
+///   dominates `b` if `a` is not worse in all objectives and better in at
+///   least one objective.
+/// + [`Fitness::eval()`] is the maximum of the objectives.
+/// + [`Fitness::objective_values()`] is the objectives.
+///
+/// ```
+/// use metaheuristics_nature::{Fitness, MultiObjective};
+///
+/// #[derive(Clone, MultiObjective)]
+/// struct MyFit {
+///     #[objective]
+///     cost: f64,
+///     #[objective]
+///     weight: f64,
+/// }
+///
+/// let a = MyFit { cost: 1., weight: 2. };
+/// let b = MyFit { cost: 1., weight: 3. };
+/// assert!(a.is_dominated(&b));
+/// assert!(!b.is_dominated(&a));
+/// assert!(!a.is_dominated(&a));
+/// assert_eq!(a.eval(), 2.);
+/// assert_eq!(a.objectives(), [1., 2.]);
+/// ```
+#[cfg(feature = "derive")]
+pub use metaheuristics_nature_derive::MultiObjective;
+
+// Used by the derive macros
+#[cfg(feature = "derive")]
+#[doc(hidden)]
+pub mod __private {
+    pub use alloc::vec::Vec;
+}
 
 /// A tool macro used to generate multiple builder functions (methods).
 ///
@@ -117,6 +177,7 @@ pub use self::{
 macro_rules! impl_builders {
     ($($(#[$meta:meta])* fn $name:ident($ty:ty))+) => {$(
         $(#[$meta])*
+        #[allow(clippy::needless_update)]
         pub fn $name(self, $name: $ty) -> Self {
             Self { $name, ..self }
         }
@@ -142,10 +203,12 @@ mod algorithm;
 mod ctx;
 mod fitness;
 mod fx_func;
+mod island;
 pub mod methods;
 mod obj_func;
 pub mod pareto;
 pub mod random;
+mod schedule;
 mod solver;
 mod solver_builder;
 pub mod tests;
@@ -2,16 +2,31 @@
 //!
 //! Each methods are also has some variants on implementation,
 //! current methods are just designed for application.
+#[allow(deprecated)]
 pub use self::{
-    de::{De, Strategy},
+    abc::Abc,
+    ba::Ba,
+    cmaes::Cmaes,
+    cs::Cs,
+    de::{De, Formula, Strategy},
     fa::Fa,
-    pso::Pso,
-    rga::Rga,
+    hs::Hs,
+    nsga2::Nsga2,
+    pso::{Pso, Topology},
+    rga::{Crossover, Rga},
     tlbo::Tlbo,
+    woa::Woa,
 };
 
+pub mod abc;
+pub mod ba;
+pub mod cmaes;
+pub mod cs;
 pub mod de;
 pub mod fa;
+pub mod hs;
+pub mod nsga2;
 pub mod pso;
 pub mod rga;
 pub mod tlbo;
+pub mod woa;
@@ -0,0 +1,173 @@
+//! # Artificial Bee Colony
+//!
+//! <https://en.wikipedia.org/wiki/Artificial_bee_colony_algorithm>
+use crate::prelude::*;
+use alloc::vec::Vec;
+use core::iter::zip;
+
+const DEF: Abc = Abc { limit: 100 };
+
+/// Artificial Bee Colony settings.
+///
+/// The onlooker bees choose the food sources by the fitness-proportionate
+/// selection, where the fitness of a source is `1 / (1 + f)` for the
+/// evaluation value `f >= 0`, and `1 + |f|` otherwise.
+/// If the evaluation value cannot be converted to a floating-point number (see
+/// [`EvalValue::to_f64()`]), the rank of the sources is used instead.
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "clap", derive(clap::Args))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct Abc {
+    /// Abandonment limit
+    #[cfg_attr(feature = "clap", clap(long, default_value_t = DEF.limit))]
+    pub limit: usize,
+}
+
+impl Abc {
+    /// Constant default value.
+    pub const fn new() -> Self {
+        DEF
+    }
+
+    impl_builders! {
+        /// Abandonment limit of the food sources.
+        fn limit(usize)
+    }
+}
+
+impl Default for Abc {
+    fn default() -> Self {
+        DEF
+    }
+}
+
+impl AlgCfg for Abc {
+    type Algorithm<F: ObjFunc> = Method;
+    fn algorithm<F: ObjFunc>(self) -> Self::Algorithm<F> {
+        Method { abc: self, trial: Vec::new() }
+    }
+}
+
+/// Algorithm of the Artificial Bee Colony.
+#[derive(Clone)]
+pub struct Method {
+    abc: Abc,
+    trial: Vec<usize>,
+}
+
+impl core::ops::Deref for Method {
+    type Target = Abc;
+
+    fn deref(&self) -> &Self::Target {
+        &self.abc
+    }
+}
+
+fn search<F: ObjFunc>(ctx: &Ctx<F>, rng: &mut Rng, i: usize) -> (Vec<f64>, F::Ys) {
+    // The neighbor is different from the source, unless it is the only one
+    let k = match ctx.pop_num() {
+        1 => i,
+        n => {
+            let k = rng.ub(n - 1);
+            if k >= i {
+                k + 1
+            } else {
+                k
+            }
+        }
+    };
+    let s = rng.ub(ctx.dim());
+    let mut xs = ctx.pool[i].clone();
+    let v = xs[s] + rng.range(-1.0..=1.0) * (xs[s] - ctx.pool[k][s]);
+    xs[s] = ctx.clamp(s, v);
+    let ys = ctx.fitness(&xs);
+    (xs, ys)
+}
+
+impl<F: ObjFunc> Algorithm<F> for Method {
+    fn init(&mut self, ctx: &mut Ctx<F>, _: &mut Rng) {
+        self.trial = alloc::vec![0; ctx.pop_num()];
+    }
+
+    fn generation(&mut self, ctx: &mut Ctx<F>, rng: &mut Rng) {
+        // The pool may be resized by the solver, see `SolverBuilder::pop_schedule()`
+        if self.trial.len() != ctx.pop_num() {
+            self.init(ctx, rng);
+        }
+        // Employed bees
+        let mut pool = ctx.pool.clone();
+        let mut pool_y = ctx.pool_y.clone();
+        let rng_s = rng.stream(ctx.pop_num());
+        #[cfg(not(feature = "rayon"))]
+        let iter = rng_s.into_iter();
+        #[cfg(feature = "rayon")]
+        let iter = rng_s.into_par_iter();
+        iter.zip(&mut pool)
+            .zip(&mut pool_y)
+            .zip(&mut self.trial)
+            .enumerate()
+            .for_each(|(i, (((mut rng, xs), ys), trial))| {
+                let (xs_new, ys_new) = search(ctx, &mut rng, i);
+                if ys_new.is_dominated_feasible(ys) {
+                    *xs = xs_new;
+                    *ys = ys_new;
+                    *trial = 0;
+                } else {
+                    *trial += 1;
+                }
+            });
+        ctx.pool = pool;
+        ctx.pool_y = pool_y;
+        // Onlooker bees
+        let weights = onlooker_weights(ctx);
+        for _ in 0..ctx.pop_num() {
+            let i = rng.weighted_index(&weights);
+            let (xs, ys) = search(ctx, rng, i);
+            if ys.is_dominated_feasible(&ctx.pool_y[i]) {
+                ctx.set_from(i, xs, ys);
+                self.trial[i] = 0;
+            } else {
+                self.trial[i] += 1;
+            }
+        }
+        // Scout bees
+        let (i, trial) = zip(0.., &mut self.trial).max_by_key(|(_, t)| **t).unwrap();
+        if *trial > self.abc.limit {
+            let xs = (0..ctx.dim())
+                .map(|s| rng.range(ctx.bound_range(s)))
+                .collect::<Vec<_>>();
+            let ys = ctx.fitness(&xs);
+            ctx.set_from(i, xs, ys);
+            *trial = 0;
+        }
+        ctx.find_best();
+    }
+}
+
+// The selection probability of the onlooker bees is proportional to the
+// fitness of the sources, `1 / (1 + f)` for `f >= 0` and `1 + |f|` otherwise.
+// The evaluation values that cannot be converted to floating-point numbers (see
+// `EvalValue::to_f64()`) fall back to the rank of the sources.
+fn onlooker_weights<F: ObjFunc>(ctx: &Ctx<F>) -> Vec<f64> {
+    let weights = (ctx.pool_y.iter())
+        .map(|ys| {
+            let f = ys.eval().to_f64()?;
+            let w = if f >= 0. { 1. / (1. + f) } else { 1. + f.abs() };
+            w.is_finite().then_some(w)
+        })
+        .collect::<Option<Vec<_>>>();
+    if let Some(weights) = weights.filter(|w| w.iter().any(|w| *w > 0.)) {
+        return weights;
+    }
+    let mut rank = (0..ctx.pop_num()).collect::<Vec<_>>();
+    rank.sort_by(|a, b| {
+        let (a, b) = (ctx.pool_y[*a].eval(), ctx.pool_y[*b].eval());
+        cmp_eval(&b, &a)
+    });
+    let mut weights = alloc::vec![0.; ctx.pop_num()];
+    for (w, i) in rank.into_iter().enumerate() {
+        weights[i] = (w + 1) as f64;
+    }
+    weights
+}
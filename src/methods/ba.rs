@@ -0,0 +1,154 @@
+//! # Bat Algorithm
+//!
+//! <https://doi.org/10.1007/978-3-642-12538-6_6>
+//!
+//! This method require floating point power functions.
+use crate::prelude::*;
+use alloc::vec::Vec;
+
+const DEF: Ba = Ba { f_min: 0., f_max: 2., alpha: 0.9, gamma: 0.9 };
+
+/// Bat Algorithm settings.
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "clap", derive(clap::Args))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct Ba {
+    /// Minimum frequency
+    #[cfg_attr(feature = "clap", clap(long, default_value_t = DEF.f_min))]
+    pub f_min: f64,
+    /// Maximum frequency
+    #[cfg_attr(feature = "clap", clap(long, default_value_t = DEF.f_max))]
+    pub f_max: f64,
+    /// Decreasing factor of the loudness
+    #[cfg_attr(feature = "clap", clap(long, default_value_t = DEF.alpha))]
+    pub alpha: f64,
+    /// Increasing factor of the pulse rate
+    #[cfg_attr(feature = "clap", clap(long, default_value_t = DEF.gamma))]
+    pub gamma: f64,
+}
+
+impl Ba {
+    /// Constant default value.
+    pub const fn new() -> Self {
+        DEF
+    }
+
+    impl_builders! {
+        /// Minimum frequency.
+        fn f_min(f64)
+        /// Maximum frequency.
+        fn f_max(f64)
+        /// Decreasing factor of the loudness, in `(0, 1)`.
+        fn alpha(f64)
+        /// Increasing factor of the pulse rate, positive.
+        fn gamma(f64)
+    }
+}
+
+impl Default for Ba {
+    fn default() -> Self {
+        DEF
+    }
+}
+
+impl AlgCfg for Ba {
+    type Algorithm<F: ObjFunc> = Method;
+    fn algorithm<F: ObjFunc>(self) -> Self::Algorithm<F> {
+        Method {
+            ba: self,
+            vel: Vec::new(),
+            loudness: Vec::new(),
+            pulse: Vec::new(),
+            pulse0: Vec::new(),
+        }
+    }
+    fn pop_num() -> usize {
+        50
+    }
+}
+
+/// Algorithm of the Bat Algorithm.
+#[derive(Clone)]
+pub struct Method {
+    ba: Ba,
+    vel: Vec<Vec<f64>>,
+    loudness: Vec<f64>,
+    pulse: Vec<f64>,
+    pulse0: Vec<f64>,
+}
+
+impl core::ops::Deref for Method {
+    type Target = Ba;
+
+    fn deref(&self) -> &Self::Target {
+        &self.ba
+    }
+}
+
+impl<F: ObjFunc> Algorithm<F> for Method {
+    fn init(&mut self, ctx: &mut Ctx<F>, rng: &mut Rng) {
+        let n = ctx.pop_num();
+        self.vel = alloc::vec![alloc::vec![0.; ctx.dim()]; n];
+        self.loudness = (0..n).map(|_| rng.range(1.0..2.)).collect();
+        self.pulse0 = (0..n).map(|_| rng.rand()).collect();
+        self.pulse = self.pulse0.clone();
+    }
+
+    fn generation(&mut self, ctx: &mut Ctx<F>, rng: &mut Rng) {
+        // The pool may be resized by the solver, see `SolverBuilder::pop_schedule()`
+        if self.vel.len() != ctx.pop_num() {
+            self.init(ctx, rng);
+        }
+        let rng = rng.stream(ctx.pop_num());
+        let Ba { f_min, f_max, alpha, gamma } = self.ba;
+        let loud_mean = self.loudness.iter().sum::<f64>() / self.loudness.len() as f64;
+        let pulse_rate = 1. - (-gamma * ctx.gen as f64).exp();
+        #[cfg(not(feature = "rayon"))]
+        let iter = rng.into_iter();
+        #[cfg(feature = "rayon")]
+        let iter = rng.into_par_iter();
+        let mut pool = core::mem::take(&mut ctx.pool);
+        let mut pool_y = core::mem::take(&mut ctx.pool_y);
+        let trials = iter
+            .zip(&mut pool)
+            .zip(&mut pool_y)
+            .zip(&mut self.vel)
+            .zip(&mut self.loudness)
+            .zip(&mut self.pulse)
+            .zip(&self.pulse0)
+            .map(|((((((mut rng, xs), ys), vel), loud), pulse), pulse0)| {
+                let best = ctx.best.sample_xs(&mut rng);
+                let freq = rng.range(f_min..=f_max);
+                let local = rng.rand() > *pulse;
+                let trial = (0..ctx.dim())
+                    .map(|s| {
+                        vel[s] += (best[s] - xs[s]) * freq;
+                        let x = if local {
+                            // Random walk around the best, scaled by the distance
+                            best[s] + rng.range(-1.0..=1.) * loud_mean * (xs[s] - best[s])
+                        } else {
+                            xs[s] + vel[s]
+                        };
+                        ctx.clamp(s, x)
+                    })
+                    .collect::<Vec<_>>();
+                let trial_y = ctx.fitness(&trial);
+                if rng.rand() < *loud && trial_y.is_dominated_feasible(ys) {
+                    *xs = trial.clone();
+                    *ys = trial_y.clone();
+                    *loud *= alpha;
+                    *pulse = pulse0 * pulse_rate;
+                }
+                (trial, trial_y)
+            })
+            .collect::<Vec<_>>();
+        ctx.pool = pool;
+        ctx.pool_y = pool_y;
+        // The best is updated even if the trial is rejected by the loudness
+        for (xs, ys) in &trials {
+            ctx.best.update(xs, ys);
+        }
+        ctx.find_best();
+    }
+}
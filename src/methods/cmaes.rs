@@ -0,0 +1,254 @@
+//! # Covariance Matrix Adaptation Evolution Strategy
+//!
+//! <https://en.wikipedia.org/wiki/CMA-ES>
+//!
+//! This method require floating point power functions.
+use crate::prelude::*;
+use alloc::{vec, vec::Vec};
+use core::iter::zip;
+
+const DEF: Cmaes = Cmaes { sigma: 0.3 };
+
+/// Covariance Matrix Adaptation Evolution Strategy settings.
+///
+/// This is the (μ/μ_w, λ)-CMA-ES, where the population size is λ and the best
+/// half of the offspring are recombined (μ = λ / 2). The variables are
+/// normalized by their bounds, so the step size is the ratio of the bound
+/// width. The offspring out of bounds are projected onto the bounds.
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "clap", derive(clap::Args))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct Cmaes {
+    /// Initial step size
+    #[cfg_attr(feature = "clap", clap(long, default_value_t = DEF.sigma))]
+    pub sigma: f64,
+}
+
+impl Cmaes {
+    /// Constant default value.
+    pub const fn new() -> Self {
+        DEF
+    }
+
+    impl_builders! {
+        /// Initial step size, as a fraction of the bound width.
+        fn sigma(f64)
+    }
+}
+
+impl Default for Cmaes {
+    fn default() -> Self {
+        DEF
+    }
+}
+
+impl AlgCfg for Cmaes {
+    type Algorithm<F: ObjFunc> = Method;
+    fn algorithm<F: ObjFunc>(self) -> Self::Algorithm<F> {
+        Method {
+            sigma: self.sigma,
+            cmaes: self,
+            mean: Vec::new(),
+            c: Vec::new(),
+            b: Vec::new(),
+            d: Vec::new(),
+            pc: Vec::new(),
+            ps: Vec::new(),
+        }
+    }
+    fn pop_num() -> usize {
+        20
+    }
+}
+
+/// Algorithm of the Covariance Matrix Adaptation Evolution Strategy.
+#[derive(Clone)]
+pub struct Method {
+    cmaes: Cmaes,
+    sigma: f64,
+    // Mean vector (normalized)
+    mean: Vec<f64>,
+    // Covariance matrix
+    c: Vec<Vec<f64>>,
+    // Eigenvectors (columns) and the square roots of the eigenvalues of C
+    b: Vec<Vec<f64>>,
+    d: Vec<f64>,
+    // Evolution paths of C and sigma
+    pc: Vec<f64>,
+    ps: Vec<f64>,
+}
+
+impl core::ops::Deref for Method {
+    type Target = Cmaes;
+
+    fn deref(&self) -> &Self::Target {
+        &self.cmaes
+    }
+}
+
+impl Method {
+    /// Current step size.
+    pub fn step_size(&self) -> f64 {
+        self.sigma
+    }
+}
+
+impl<F: ObjFunc> Algorithm<F> for Method {
+    fn init(&mut self, ctx: &mut Ctx<F>, rng: &mut Rng) {
+        let n = ctx.dim();
+        self.sigma = self.cmaes.sigma;
+        self.mean = zip(ctx.best.sample_xs(rng), ctx.bound())
+            .map(|(x, [min, max])| (x - min) / (max - min))
+            .collect();
+        self.c = identity(n);
+        self.b = identity(n);
+        self.d = vec![1.; n];
+        self.pc = vec![0.; n];
+        self.ps = vec![0.; n];
+    }
+
+    fn generation(&mut self, ctx: &mut Ctx<F>, rng: &mut Rng) {
+        let n = ctx.dim();
+        let nf = n as f64;
+        let lambda = ctx.pop_num();
+        let mu = (lambda / 2).max(1);
+        // Recombination weights
+        let mut w = (0..mu)
+            .map(|i| (mu as f64 + 0.5).ln() - (i as f64 + 1.).ln())
+            .collect::<Vec<_>>();
+        let w_sum = w.iter().sum::<f64>();
+        w.iter_mut().for_each(|w| *w /= w_sum);
+        let mueff = 1. / w.iter().map(|w| w * w).sum::<f64>();
+        // Learning rates
+        let cc = (4. + mueff / nf) / (nf + 4. + 2. * mueff / nf);
+        let cs = (mueff + 2.) / (nf + mueff + 5.);
+        let c1 = 2. / ((nf + 1.3).powi(2) + mueff);
+        let cmu = (2. * (mueff - 2. + 1. / mueff) / ((nf + 2.).powi(2) + mueff)).min(1. - c1);
+        let damps = 1. + 2. * (((mueff - 1.) / (nf + 1.)).sqrt() - 1.).max(0.) + cs;
+        let chi_n = nf.sqrt() * (1. - 1. / (4. * nf) + 1. / (21. * nf * nf));
+        // Sample offspring: x = m + sigma * B * D * z
+        let pool = (0..lambda)
+            .map(|_| {
+                let dz = zip(&self.d, (0..n).map(|_| rng.normal(0., 1.)))
+                    .map(|(d, z)| d * z)
+                    .collect::<Vec<_>>();
+                (0..n)
+                    .map(|s| {
+                        let y = zip(&self.b[s], &dz).map(|(b, dz)| b * dz).sum::<f64>();
+                        (self.mean[s] + self.sigma * y).clamp(0., 1.)
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        ctx.pool = pool.iter().map(|u| denormalize(ctx, u)).collect();
+        ctx.pool_y = ctx.fitness_batch(&ctx.pool);
+        ctx.find_best();
+        // Selection and recombination
+        let best = ctx.best_indices(mu);
+        let mean_old = core::mem::take(&mut self.mean);
+        self.mean = (0..n)
+            .map(|s| zip(&w, &best).map(|(w, &i)| w * pool[i][s]).sum())
+            .collect();
+        let y_w = zip(&self.mean, &mean_old)
+            .map(|(m, m_old)| (m - m_old) / self.sigma)
+            .collect::<Vec<_>>();
+        // Update the evolution paths
+        let inv_sqrt_c_y = {
+            let bt_y = (0..n)
+                .map(|k| (0..n).map(|s| self.b[s][k] * y_w[s]).sum::<f64>() / self.d[k])
+                .collect::<Vec<_>>();
+            (0..n)
+                .map(|s| zip(&self.b[s], &bt_y).map(|(b, y)| b * y).sum::<f64>())
+                .collect::<Vec<_>>()
+        };
+        let k = (cs * (2. - cs) * mueff).sqrt();
+        zip(&mut self.ps, inv_sqrt_c_y).for_each(|(ps, y)| *ps = (1. - cs) * *ps + k * y);
+        let ps_norm = self.ps.iter().map(|p| p * p).sum::<f64>().sqrt();
+        let gen = ctx.gen.max(1) as i32;
+        let hsig = ps_norm / (1. - (1. - cs).powi(2 * gen)).sqrt() / chi_n < 1.4 + 2. / (nf + 1.);
+        let hsig = if hsig { 1. } else { 0. };
+        let k = hsig * (cc * (2. - cc) * mueff).sqrt();
+        zip(&mut self.pc, &y_w).for_each(|(pc, y)| *pc = (1. - cc) * *pc + k * y);
+        // Update the covariance matrix
+        let ys = best
+            .iter()
+            .map(|&i| {
+                zip(&pool[i], &mean_old)
+                    .map(|(x, m)| (x - m) / self.sigma)
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let decay = 1. - c1 - cmu + (1. - hsig) * c1 * cc * (2. - cc);
+        for i in 0..n {
+            for j in 0..=i {
+                let rank_mu = zip(&w, &ys).map(|(w, y)| w * y[i] * y[j]).sum::<f64>();
+                let v = decay * self.c[i][j] + c1 * self.pc[i] * self.pc[j] + cmu * rank_mu;
+                self.c[i][j] = v;
+                self.c[j][i] = v;
+            }
+        }
+        // Update the step size
+        self.sigma *= ((cs / damps) * (ps_norm / chi_n - 1.)).exp();
+        // Decompose C = B * D^2 * B^T
+        let (b, eig) = eigen(&self.c);
+        self.b = b;
+        self.d = eig
+            .into_iter()
+            .map(|e| e.max(f64::EPSILON).sqrt())
+            .collect();
+    }
+}
+
+fn identity(n: usize) -> Vec<Vec<f64>> {
+    (0..n)
+        .map(|i| (0..n).map(|j| if i == j { 1. } else { 0. }).collect())
+        .collect()
+}
+
+fn denormalize<F: ObjFunc>(ctx: &Ctx<F>, u: &[f64]) -> Vec<f64> {
+    zip(u, ctx.bound())
+        .map(|(u, [min, max])| min + u * (max - min))
+        .collect()
+}
+
+// Eigendecomposition of a symmetric matrix with the cyclic Jacobi method,
+// returns the eigenvectors (columns) and the eigenvalues
+fn eigen(a: &[Vec<f64>]) -> (Vec<Vec<f64>>, Vec<f64>) {
+    let n = a.len();
+    let mut a = a.to_vec();
+    let mut v = identity(n);
+    for _ in 0..50 {
+        let off = (0..n)
+            .flat_map(|i| (0..i).map(move |j| (i, j)))
+            .map(|(i, j)| a[i][j] * a[i][j])
+            .sum::<f64>();
+        if off < 1e-30 {
+            break;
+        }
+        for p in 0..n {
+            for q in p + 1..n {
+                if a[p][q].abs() < 1e-300 {
+                    continue;
+                }
+                let theta = (a[q][q] - a[p][p]) / (2. * a[p][q]);
+                let t = theta.signum() / (theta.abs() + (theta * theta + 1.).sqrt());
+                let c = 1. / (t * t + 1.).sqrt();
+                let s = t * c;
+                // A = J^T * A * J, V = V * J
+                for row in a.iter_mut().chain(v.iter_mut()) {
+                    let (xp, xq) = (row[p], row[q]);
+                    row[p] = c * xp - s * xq;
+                    row[q] = s * xp + c * xq;
+                }
+                let (ap, aq) = (a[p].clone(), a[q].clone());
+                for (k, (apk, aqk)) in zip(ap, aq).enumerate() {
+                    a[p][k] = c * apk - s * aqk;
+                    a[q][k] = s * apk + c * aqk;
+                }
+            }
+        }
+    }
+    let eig = (0..n).map(|i| a[i][i]).collect();
+    (v, eig)
+}
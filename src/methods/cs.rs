@@ -0,0 +1,114 @@
+//! # Cuckoo Search
+//!
+//! <https://doi.org/10.1109/NABIC.2009.5393690>
+//!
+//! This method require floating point power and trigonometric functions.
+use crate::prelude::*;
+use alloc::vec::Vec;
+use core::iter::zip;
+
+/// Algorithm of the Cuckoo Search.
+pub type Method = Cs;
+
+const DEF: Cs = Cs { pa: 0.25, alpha: 1. };
+
+/// Cuckoo Search settings.
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "clap", derive(clap::Args))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct Cs {
+    /// Discovery rate of alien eggs, in `0..=1`
+    #[cfg_attr(feature = "clap", clap(long, default_value_t = DEF.pa))]
+    pub pa: f64,
+    /// Step size of the Lévy flight
+    #[cfg_attr(feature = "clap", clap(long, default_value_t = DEF.alpha))]
+    pub alpha: f64,
+}
+
+impl Cs {
+    /// Constant default value.
+    pub const fn new() -> Self {
+        DEF
+    }
+
+    impl_builders! {
+        /// Fraction of the worst nests to be abandoned in each generation.
+        ///
+        /// The value is clamped to `0..=1`.
+        fn pa(f64)
+        /// Step size of the Lévy flight, scaled by the distance to the best
+        /// nest.
+        fn alpha(f64)
+    }
+}
+
+impl Default for Cs {
+    fn default() -> Self {
+        DEF
+    }
+}
+
+impl AlgCfg for Cs {
+    type Algorithm<F: ObjFunc> = Method;
+    fn algorithm<F: ObjFunc>(self) -> Self::Algorithm<F> {
+        self
+    }
+    fn pop_num() -> usize {
+        50
+    }
+}
+
+impl<F: ObjFunc> Algorithm<F> for Method {
+    fn generation(&mut self, ctx: &mut Ctx<F>, rng: &mut Rng) {
+        // Lay eggs by Lévy flights
+        let rng_s = rng.stream(ctx.pop_num());
+        #[cfg(not(feature = "rayon"))]
+        let iter = rng_s.into_iter();
+        #[cfg(feature = "rayon")]
+        let iter = rng_s.into_par_iter();
+        let eggs = iter
+            .map(|mut rng| {
+                let i = rng.ub(ctx.pop_num());
+                let best = ctx.best.sample_xs(&mut rng);
+                let xs = zip(&ctx.pool[i], best)
+                    .enumerate()
+                    .map(|(s, (x, best))| {
+                        let step = self.alpha * rng.levy(1.5) * (x - best);
+                        ctx.clamp(s, x + step * rng.normal(0., 1.))
+                    })
+                    .collect::<Vec<_>>();
+                let ys = ctx.fitness(&xs);
+                (xs, ys)
+            })
+            .collect::<Vec<_>>();
+        for (xs, ys) in eggs {
+            let j = rng.ub(ctx.pop_num());
+            if ys.is_dominated_feasible(&ctx.pool_y[j]) {
+                ctx.set_from(j, xs, ys);
+            }
+        }
+        // Abandon the worst nests
+        let mut rank = ctx.rank();
+        let n = (self.pa.clamp(0., 1.) * ctx.pop_num() as f64) as usize;
+        let worst = rank.split_off(ctx.pop_num() - n);
+        let rng_s = rng.stream(n);
+        #[cfg(not(feature = "rayon"))]
+        let iter = rng_s.into_iter();
+        #[cfg(feature = "rayon")]
+        let iter = rng_s.into_par_iter();
+        let nests = iter
+            .map(|mut rng| {
+                let xs = (0..ctx.dim())
+                    .map(|s| rng.range(ctx.bound_range(s)))
+                    .collect::<Vec<_>>();
+                let ys = ctx.fitness(&xs);
+                (xs, ys)
+            })
+            .collect::<Vec<_>>();
+        for (i, (xs, ys)) in zip(worst, nests) {
+            ctx.set_from(i, xs, ys);
+        }
+        ctx.find_best();
+    }
+}
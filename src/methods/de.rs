@@ -1,36 +1,77 @@
 //! # Differential Evolution
 //!
 //! <https://en.wikipedia.org/wiki/Differential_evolution>
-use self::Strategy::*;
+// The deprecated strategy is still accepted by the settings
+#![allow(deprecated)]
+use self::{Crossover::*, Formula::*};
 use crate::prelude::*;
-use alloc::{boxed::Box, vec::Vec};
+use alloc::{boxed::Box, collections::VecDeque, vec::Vec};
 
-/// Algorithm of the Differential Evolution.
-pub type Method = De;
 type Func<F> = Box<dyn Fn(&Ctx<F>, &[f64], usize) -> f64>;
+type Record = [[u32; 2]; STRATEGY_NUM];
 
-const DEF: De = De { strategy: C1F1, f: 0.6, cross: 0.9 };
+const DEF: De = De {
+    formula: F1,
+    crossover: Exponential,
+    f: 0.6,
+    cross: 0.9,
+    self_adaptive: false,
+};
+/// Learning period (generations) of the self-adaptive mode.
+const WINDOW: usize = 50;
+/// Number of the combinations of the formulas and the crossovers.
+const STRATEGY_NUM: usize = Formula::LIST.len() * Crossover::LIST.len();
 
-/// The Differential Evolution strategy.
-///
-/// Each strategy has different formulas on the recombination.
-///
-/// # Variable formula
+/// The variable formula of the Differential Evolution.
 ///
 /// This formula decide how to generate new variable *n*.
 /// Where *vi* is the random indicator on the individuals,
 /// they are different from each other.
-///
-/// + *f1*: best{n} + F * (v0{n} - v1{n})
-/// + *f2*: v0{n} + F * (v1{n} - v2{n})
-/// + *f3*: self{n} + F * (best{n} - self{n} + v0{n} - v1{n})
-/// + *f4*: best{n} + F * (v0{n} + v1{n} - v2{n} - v3{n})
-/// + *f5*: v4{n} + F * (v0{n} + v1{n} - v2{n} - v3{n})
-///
-/// # Crossover formula
-///
-/// + *c1*: Continue crossover in order until end with probability.
-/// + *c2*: Each variable has independent probability.
+#[derive(Default, Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Formula {
+    /// best{n} + F * (v0{n} - v1{n})
+    #[default]
+    F1,
+    /// v0{n} + F * (v1{n} - v2{n})
+    F2,
+    /// self{n} + F * (best{n} - self{n} + v0{n} - v1{n}) (current-to-best/1)
+    F3,
+    /// best{n} + F * (v0{n} + v1{n} - v2{n} - v3{n})
+    F4,
+    /// v4{n} + F * (v0{n} + v1{n} - v2{n} - v3{n})
+    F5,
+    /// v2{n} + F * (best{n} - v2{n} + v0{n} - v1{n}) (rand-to-best/1)
+    F6,
+}
+
+impl Formula {
+    /// A list of all formulas.
+    pub const LIST: [Self; 6] = [F1, F2, F3, F4, F5, F6];
+}
+
+/// The crossover of the Differential Evolution.
+#[derive(Default, Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Crossover {
+    /// Continue crossover in order until end with probability.
+    #[default]
+    Exponential,
+    /// Each variable has independent probability.
+    Binomial,
+}
+
+impl Crossover {
+    /// A list of all crossovers.
+    pub const LIST: [Self; 2] = [Exponential, Binomial];
+}
+
+/// The Differential Evolution strategy, the combination of the [`Formula`]
+/// (*f1* to *f6*) and the [`Crossover`] (*c1* is [`Crossover::Exponential`]
+/// and *c2* is [`Crossover::Binomial`]).
+#[deprecated = "use `Formula` and `Crossover` instead"]
 #[derive(Default, Copy, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -46,6 +87,8 @@ pub enum Strategy {
     C1F4,
     /// *f5* + *c1*
     C1F5,
+    /// *f6* + *c1*
+    C1F6,
     /// *f1* + *c2*
     C2F1,
     /// *f2* + *c2*
@@ -56,28 +99,145 @@ pub enum Strategy {
     C2F4,
     /// *f5* + *c2*
     C2F5,
+    /// *f6* + *c2*
+    C2F6,
 }
 
 impl Strategy {
     /// A list of all strategies.
-    pub const LIST: [Self; 10] = [C1F1, C1F2, C1F3, C1F4, C1F5, C2F1, C2F2, C2F3, C2F4, C2F5];
+    pub const LIST: [Self; 12] = {
+        use Strategy::*;
+        [
+            C1F1, C1F2, C1F3, C1F4, C1F5, C1F6, C2F1, C2F2, C2F3, C2F4, C2F5, C2F6,
+        ]
+    };
+
+    /// The variable formula of the strategy.
+    pub fn formula(self) -> Formula {
+        Formula::LIST[self as usize % Formula::LIST.len()]
+    }
+
+    /// The crossover of the strategy.
+    pub fn crossover(self) -> Crossover {
+        Crossover::LIST[self as usize / Formula::LIST.len()]
+    }
 }
 
 /// Differential Evolution settings.
+///
+/// The deprecated `strategy` option is still accepted by the deserialization
+/// and the command line (`--strategy`), which overrides the formula and the
+/// crossover, see [`Strategy`].
 #[derive(Clone, PartialEq)]
-#[cfg_attr(feature = "clap", derive(clap::Args))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-#[cfg_attr(feature = "serde", serde(default))]
+#[cfg_attr(feature = "serde", serde(from = "DeCompat"))]
 pub struct De {
-    /// Strategy
-    #[cfg_attr(feature = "clap", clap(long, value_enum, default_value_t = DEF.strategy))]
-    pub strategy: Strategy,
+    /// Variable formula
+    pub formula: Formula,
+    /// Crossover
+    pub crossover: Crossover,
     /// F factor in the formula
-    #[cfg_attr(feature = "clap", clap(long, default_value_t = DEF.f))]
     pub f: f64,
     /// Crossover rate
-    #[cfg_attr(feature = "clap", clap(long, default_value_t = DEF.cross))]
     pub cross: f64,
+    /// Select the strategy of each individual by the success rates
+    pub self_adaptive: bool,
+}
+
+// The settings with the deprecated strategy option
+#[derive(Clone)]
+#[cfg_attr(feature = "clap", derive(clap::Args))]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+struct DeCompat {
+    /// Variable formula
+    #[cfg_attr(feature = "clap", clap(long, value_enum, default_value_t = DEF.formula))]
+    formula: Formula,
+    /// Crossover
+    #[cfg_attr(feature = "clap", clap(long, value_enum, default_value_t = DEF.crossover))]
+    crossover: Crossover,
+    /// F factor in the formula
+    #[cfg_attr(feature = "clap", clap(long, default_value_t = DEF.f))]
+    f: f64,
+    /// Crossover rate
+    #[cfg_attr(feature = "clap", clap(long, default_value_t = DEF.cross))]
+    cross: f64,
+    /// Select the strategy of each individual by the success rates
+    #[cfg_attr(feature = "clap", clap(long))]
+    self_adaptive: bool,
+    /// Deprecated, use "--formula" and "--crossover" instead
+    #[cfg_attr(feature = "clap", clap(long, value_enum, hide = true))]
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "strategy"))]
+    strategy: Option<Strategy>,
+}
+
+// The deprecated field is not optional
+#[cfg(feature = "serde")]
+fn strategy<'de, D: serde::Deserializer<'de>>(de: D) -> Result<Option<Strategy>, D::Error> {
+    serde::Deserialize::deserialize(de).map(Some)
+}
+
+impl Default for DeCompat {
+    fn default() -> Self {
+        Self::from(DEF)
+    }
+}
+
+impl From<De> for DeCompat {
+    fn from(de: De) -> Self {
+        let De { formula, crossover, f, cross, self_adaptive } = de;
+        Self {
+            formula,
+            crossover,
+            f,
+            cross,
+            self_adaptive,
+            strategy: None,
+        }
+    }
+}
+
+impl From<DeCompat> for De {
+    fn from(de: DeCompat) -> Self {
+        let DeCompat {
+            formula,
+            crossover,
+            f,
+            cross,
+            self_adaptive,
+            strategy,
+        } = de;
+        let de = Self { formula, crossover, f, cross, self_adaptive };
+        match strategy {
+            Some(strategy) => de.strategy(strategy),
+            None => de,
+        }
+    }
+}
+
+#[cfg(feature = "clap")]
+impl clap::FromArgMatches for De {
+    fn from_arg_matches(matches: &clap::ArgMatches) -> Result<Self, clap::Error> {
+        DeCompat::from_arg_matches(matches).map(Self::from)
+    }
+
+    fn update_from_arg_matches(&mut self, matches: &clap::ArgMatches) -> Result<(), clap::Error> {
+        let mut de = DeCompat::from(self.clone());
+        de.update_from_arg_matches(matches)?;
+        *self = de.into();
+        Ok(())
+    }
+}
+
+#[cfg(feature = "clap")]
+impl clap::Args for De {
+    fn augment_args(cmd: clap::Command) -> clap::Command {
+        DeCompat::augment_args(cmd)
+    }
+
+    fn augment_args_for_update(cmd: clap::Command) -> clap::Command {
+        DeCompat::augment_args_for_update(cmd)
+    }
 }
 
 impl De {
@@ -87,12 +247,32 @@ impl De {
     }
 
     impl_builders! {
-        /// Strategy of the formula.
-        fn strategy(Strategy)
+        /// Variable formula.
+        fn formula(Formula)
+        /// Crossover, which is independent of the formula.
+        fn crossover(Crossover)
         /// F factor.
         fn f(f64)
         /// Crossing probability.
         fn cross(f64)
+        /// Self-adaptive strategy selection (SaDE).
+        ///
+        /// If enabled, the formula and the crossover of each individual are
+        /// sampled from all of their combinations in each generation,
+        /// weighted by the success rates of the combinations in the recent
+        /// generations. The [`De::formula`] and [`De::crossover`] options are
+        /// ignored.
+        fn self_adaptive(bool)
+    }
+
+    /// Strategy of the formula and the crossover.
+    #[deprecated = "use `De::formula()` and `De::crossover()` instead"]
+    pub fn strategy(self, strategy: Strategy) -> Self {
+        Self {
+            formula: strategy.formula(),
+            crossover: strategy.crossover(),
+            ..self
+        }
     }
 }
 
@@ -105,32 +285,66 @@ impl Default for De {
 impl AlgCfg for De {
     type Algorithm<F: ObjFunc> = Method;
     fn algorithm<F: ObjFunc>(self) -> Self::Algorithm<F> {
-        self
+        Method { de: self, history: VecDeque::new() }
     }
     fn pop_num() -> usize {
         400
     }
 }
 
+/// Algorithm of the Differential Evolution.
+#[derive(Clone)]
+pub struct Method {
+    de: De,
+    // Success and failure counts of each strategy in the recent generations
+    history: VecDeque<Record>,
+}
+
+impl core::ops::Deref for Method {
+    type Target = De;
+
+    fn deref(&self) -> &Self::Target {
+        &self.de
+    }
+}
+
 impl Method {
-    fn formula<F: ObjFunc>(&self, ctx: &Ctx<F>, rng: &mut Rng) -> Func<F> {
+    /// Success probabilities of the strategies over the recent generations.
+    fn probs(&self) -> Vec<f64> {
+        let mut sum = [[0; 2]; STRATEGY_NUM];
+        for record in &self.history {
+            for (sum, [ns, nf]) in sum.iter_mut().zip(record) {
+                sum[0] += ns;
+                sum[1] += nf;
+            }
+        }
+        sum.into_iter()
+            .map(|[ns, nf]| match ns + nf {
+                // A small constant to keep every strategy selectable
+                0 => 1.,
+                n => ns as f64 / n as f64 + 0.01,
+            })
+            .collect()
+    }
+
+    fn formula_fn<F: ObjFunc>(&self, ctx: &Ctx<F>, rng: &mut Rng, formula: Formula) -> Func<F> {
         let f = self.f;
-        match self.strategy {
-            C1F1 | C2F1 => {
+        match formula {
+            F1 => {
                 let [v0, v1] = rng.array(0..ctx.pop_num());
                 let best = ctx.best.sample_xs(rng).to_vec();
                 Box::new(move |ctx, _, s| best[s] + f * (ctx.pool[v0][s] - ctx.pool[v1][s]))
             }
-            C1F2 | C2F2 => Box::new({
+            F2 => Box::new({
                 let [v0, v1, v2] = rng.array(0..ctx.pop_num());
                 move |ctx, _, s| ctx.pool[v0][s] + f * (ctx.pool[v1][s] - ctx.pool[v2][s])
             }),
-            C1F3 | C2F3 => Box::new({
+            F3 => Box::new({
                 let [v0, v1] = rng.array(0..ctx.pop_num());
                 let best = ctx.best.sample_xs(rng).to_vec();
                 move |ctx, xs, s| xs[s] + f * (best[s] - xs[s] + ctx.pool[v0][s] - ctx.pool[v1][s])
             }),
-            C1F4 | C2F4 => Box::new({
+            F4 => Box::new({
                 let [v0, v1, v2, v3] = rng.array(0..ctx.pop_num());
                 let best = ctx.best.sample_xs(rng).to_vec();
                 move |ctx, _, s| {
@@ -140,7 +354,7 @@ impl Method {
                             - ctx.pool[v3][s])
                 }
             }),
-            C1F5 | C2F5 => Box::new({
+            F5 => Box::new({
                 let [v0, v1, v2, v3, v4] = rng.array(0..ctx.pop_num());
                 move |ctx, _, s| {
                     ctx.pool[v4][s]
@@ -149,6 +363,14 @@ impl Method {
                             - ctx.pool[v3][s])
                 }
             }),
+            F6 => Box::new({
+                let [v0, v1, v2] = rng.array(0..ctx.pop_num());
+                let best = ctx.best.sample_xs(rng).to_vec();
+                move |ctx, _, s| {
+                    let base = ctx.pool[v2][s];
+                    base + f * (best[s] - base + ctx.pool[v0][s] - ctx.pool[v1][s])
+                }
+            }),
         }
     }
 
@@ -182,41 +404,55 @@ impl Method {
 
 impl<F: ObjFunc> Algorithm<F> for Method {
     fn generation(&mut self, ctx: &mut Ctx<F>, rng: &mut Rng) {
-        let mut pool = ctx.pool.clone();
-        let mut pool_y = ctx.pool_y.clone();
+        // The index of the combination, in the order of crossover-major
+        let strategies = if self.self_adaptive {
+            let dist = crate::random::Categorical::new(&self.probs());
+            (0..ctx.pop_num()).map(|_| rng.sample(&dist)).collect()
+        } else {
+            let i = Formula::LIST.iter().position(|f| *f == self.formula);
+            let j = Crossover::LIST.iter().position(|c| *c == self.crossover);
+            alloc::vec![j.unwrap() * Formula::LIST.len() + i.unwrap(); ctx.pop_num()]
+        };
         let rng = rng.stream(ctx.pop_num());
         #[cfg(not(feature = "rayon"))]
         let iter = rng.into_iter();
         #[cfg(feature = "rayon")]
         let iter = rng.into_par_iter();
-        let (xs, ys): (Vec<_>, Vec<_>) = iter
-            .zip(&mut pool)
-            .zip(&mut pool_y)
-            .filter_map(|((mut rng, xs), ys)| {
+        let trials = iter
+            .zip(&ctx.pool)
+            .zip(&strategies)
+            .map(|((mut rng, xs), &k)| {
+                let formula = Formula::LIST[k % Formula::LIST.len()];
+                let crossover = Crossover::LIST[k / Formula::LIST.len()];
                 // Generate Vector
-                let formula = self.formula(ctx, &mut rng);
+                let formula = self.formula_fn(ctx, &mut rng, formula);
                 // Recombination
                 let mut xs_trial = xs.clone();
-                match self.strategy {
-                    C1F1 | C1F2 | C1F3 | C1F4 | C1F5 => {
-                        self.c1(ctx, &mut rng, &mut xs_trial, formula)
-                    }
-                    C2F1 | C2F2 | C2F3 | C2F4 | C2F5 => {
-                        self.c2(ctx, &mut rng, &mut xs_trial, formula)
-                    }
-                }
-                let ys_trial = ctx.fitness(&xs_trial);
-                if ys_trial.is_dominated(ys) {
-                    *xs = xs_trial;
-                    *ys = ys_trial;
-                    Some((&*xs, &*ys))
-                } else {
-                    None
+                match crossover {
+                    Exponential => self.c1(ctx, &mut rng, &mut xs_trial, formula),
+                    Binomial => self.c2(ctx, &mut rng, &mut xs_trial, formula),
                 }
+                xs_trial
             })
-            .unzip();
-        ctx.best.update_all(xs, ys);
-        ctx.pool = pool;
-        ctx.pool_y = pool_y;
+            .collect::<Vec<_>>();
+        // Evaluate the trials at once
+        let trials_y = ctx.fitness_batch(&trials);
+        let mut record = [[0; 2]; STRATEGY_NUM];
+        for (i, (xs, ys)) in trials.into_iter().zip(trials_y).enumerate() {
+            let k = strategies[i];
+            if ys.is_dominated_feasible(&ctx.pool_y[i]) {
+                record[k][0] += 1;
+                ctx.best.update(&xs, &ys);
+                ctx.set_from(i, xs, ys);
+            } else {
+                record[k][1] += 1;
+            }
+        }
+        if self.self_adaptive {
+            if self.history.len() == WINDOW {
+                self.history.pop_front();
+            }
+            self.history.push_back(record);
+        }
     }
 }
@@ -10,9 +10,18 @@ use core::iter::zip;
 /// Algorithm of the Firefly Algorithm.
 pub type Method = Fa;
 
-const DEF: Fa = Fa { alpha: 1., beta_min: 1., gamma: 0.01 };
+const DEF: Fa = Fa {
+    alpha: 1.,
+    beta_min: 1.,
+    gamma: 0.01,
+    alpha_schedule: None,
+    neighbors: None,
+};
 
 /// Firefly Algorithm settings.
+///
+/// By default, the alpha factor is decayed by 0.95 in each generation. Use
+/// [`Fa::alpha_schedule()`] to change the behavior.
 #[derive(Clone, PartialEq)]
 #[cfg_attr(feature = "clap", derive(clap::Args))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -27,6 +36,12 @@ pub struct Fa {
     /// Gamma factor
     #[cfg_attr(feature = "clap", clap(long, default_value_t = DEF.gamma))]
     pub gamma: f64,
+    /// Schedule of the alpha factor, overrides the alpha factor
+    #[cfg_attr(feature = "clap", clap(skip))]
+    pub alpha_schedule: Option<Schedule>,
+    /// Number of the nearest neighbors to compare with
+    #[cfg_attr(feature = "clap", clap(long))]
+    pub neighbors: Option<usize>,
 }
 
 impl Fa {
@@ -43,6 +58,27 @@ impl Fa {
         /// Gamma factor.
         fn gamma(f64)
     }
+
+    /// Schedule of the alpha factor.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{Fa, Schedule};
+    ///
+    /// let fa = Fa::default().alpha_schedule(Schedule::Linear { from: 1., to: 0.01 });
+    /// ```
+    pub fn alpha_schedule(self, alpha_schedule: Schedule) -> Self {
+        Self { alpha_schedule: Some(alpha_schedule), ..self }
+    }
+
+    /// Compare each firefly with its `k` nearest neighbors only.
+    ///
+    /// By default, every firefly is compared with all the others, which costs
+    /// `O(n^2)` evaluations per generation for the population size `n`. This
+    /// option reduces the cost to `O(n * k)`, so the method can be used with a
+    /// larger population.
+    pub fn neighbors(self, k: usize) -> Self {
+        Self { neighbors: Some(k), ..self }
+    }
 }
 
 impl Default for Fa {
@@ -66,21 +102,20 @@ impl Method {
         &self,
         ctx: &Ctx<F>,
         rng: &mut Rng,
+        alpha: f64,
         i: usize,
         j: usize,
     ) -> (Vec<f64>, F::Ys) {
-        let (i, j) = if ctx.pool_y[j].is_dominated(&ctx.pool_y[i]) {
+        let (i, j) = if ctx.pool_y[j].is_dominated_feasible(&ctx.pool_y[i]) {
             (i, j)
         } else {
             (j, i)
         };
-        let r = zip(&ctx.pool[i], &ctx.pool[j])
-            .map(|(a, b)| a - b)
-            .fold(0., |acc, x| acc + x * x);
+        let r = dist2(&ctx.pool[i], &ctx.pool[j]);
         let beta = self.beta_min * (-self.gamma * r).exp();
         let xs = zip(ctx.bound(), zip(&ctx.pool[i], &ctx.pool[j]))
             .map(|(&[min, max], (a, b))| {
-                let step = self.alpha * (max - min) * rng.range(-0.5..0.5);
+                let step = rng.scaled_step([min, max], alpha);
                 let surround = a + beta * (b - a);
                 (surround + step).clamp(min, max)
             })
@@ -88,10 +123,33 @@ impl Method {
         let ys = ctx.fitness(&xs);
         (xs, ys)
     }
+
+    /// The fireflies to be compared with the firefly `i`.
+    fn others<F: ObjFunc>(&self, ctx: &Ctx<F>, i: usize) -> Vec<usize> {
+        let Some(k) = self.neighbors else {
+            return (i + 1..ctx.pop_num()).collect();
+        };
+        let mut others = (0..ctx.pop_num()).filter(|&j| j != i).collect::<Vec<_>>();
+        if k < others.len() {
+            let d = |&j: &usize| dist2(&ctx.pool[i], &ctx.pool[j]);
+            others.select_nth_unstable_by(k, |a, b| d(a).total_cmp(&d(b)));
+            others.truncate(k);
+        }
+        others
+    }
+}
+
+/// Squared Euclidean distance.
+fn dist2(a: &[f64], b: &[f64]) -> f64 {
+    zip(a, b).map(|(a, b)| a - b).fold(0., |acc, x| acc + x * x)
 }
 
 impl<F: ObjFunc> Algorithm<F> for Method {
     fn generation(&mut self, ctx: &mut Ctx<F>, rng: &mut Rng) {
+        let alpha = match &self.alpha_schedule {
+            Some(sch) => sch.value(ctx.gen, ctx.max_gen()),
+            None => self.alpha,
+        };
         // Move fireflies
         let mut pool = ctx.pool.clone();
         let mut pool_y = ctx.pool_y.clone();
@@ -104,9 +162,9 @@ impl<F: ObjFunc> Algorithm<F> for Method {
             .zip(&mut pool_y)
             .enumerate()
             .for_each(|(i, ((mut rng, xs), ys))| {
-                for j in i + 1..ctx.pop_num() {
-                    let (xs_new, ys_new) = self.move_firefly(ctx, &mut rng, i, j);
-                    if ys_new.is_dominated(ys) {
+                for j in self.others(ctx, i) {
+                    let (xs_new, ys_new) = self.move_firefly(ctx, &mut rng, alpha, i, j);
+                    if ys_new.is_dominated_feasible(ys) {
                         *xs = xs_new;
                         *ys = ys_new;
                     }
@@ -115,6 +173,8 @@ impl<F: ObjFunc> Algorithm<F> for Method {
         ctx.pool = pool;
         ctx.pool_y = pool_y;
         ctx.find_best();
-        self.alpha *= 0.95;
+        if self.alpha_schedule.is_none() {
+            self.alpha *= 0.95;
+        }
     }
 }
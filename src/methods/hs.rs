@@ -0,0 +1,89 @@
+//! # Harmony Search
+//!
+//! <https://doi.org/10.1177/003754970107600201>
+use crate::prelude::*;
+use alloc::vec::Vec;
+
+/// Algorithm of the Harmony Search.
+pub type Method = Hs;
+
+const DEF: Hs = Hs { hmcr: 0.9, par: 0.3, bandwidth: 0.5 };
+
+/// Harmony Search settings.
+///
+/// The pool is the harmony memory, and one new harmony is improvised in each
+/// generation. The pitch adjustment step is scaled by the range of the memory
+/// on each variable, so the step is shrunk as the memory converges.
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "clap", derive(clap::Args))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct Hs {
+    /// Harmony memory considering rate
+    #[cfg_attr(feature = "clap", clap(long, default_value_t = DEF.hmcr))]
+    pub hmcr: f64,
+    /// Pitch adjusting rate
+    #[cfg_attr(feature = "clap", clap(long, default_value_t = DEF.par))]
+    pub par: f64,
+    /// Bandwidth of the pitch adjustment
+    #[cfg_attr(feature = "clap", clap(long, default_value_t = DEF.bandwidth))]
+    pub bandwidth: f64,
+}
+
+impl Hs {
+    /// Constant default value.
+    pub const fn new() -> Self {
+        DEF
+    }
+
+    impl_builders! {
+        /// Probability of choosing a note from the harmony memory, otherwise
+        /// the note is randomized.
+        fn hmcr(f64)
+        /// Probability of adjusting the pitch of a note from the memory.
+        fn par(f64)
+        /// Maximum pitch adjustment step, as a fraction of the memory range.
+        fn bandwidth(f64)
+    }
+}
+
+impl Default for Hs {
+    fn default() -> Self {
+        DEF
+    }
+}
+
+impl AlgCfg for Hs {
+    type Algorithm<F: ObjFunc> = Method;
+    fn algorithm<F: ObjFunc>(self) -> Self::Algorithm<F> {
+        self
+    }
+    fn pop_num() -> usize {
+        30
+    }
+}
+
+impl<F: ObjFunc> Algorithm<F> for Method {
+    fn generation(&mut self, ctx: &mut Ctx<F>, rng: &mut Rng) {
+        let xs = (0..ctx.dim())
+            .map(|s| {
+                if !rng.maybe(self.hmcr) {
+                    return rng.range(ctx.bound_range(s));
+                }
+                let x = ctx.pool[rng.ub(ctx.pop_num())][s];
+                if !rng.maybe(self.par) {
+                    return x;
+                }
+                let (min, max) = (ctx.pool.iter().map(|xs| xs[s]))
+                    .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), x| {
+                        (min.min(x), max.max(x))
+                    });
+                let step = rng.range(-1.0..1.) * self.bandwidth * (max - min);
+                ctx.clamp(s, x + step)
+            })
+            .collect::<Vec<_>>();
+        let ys = ctx.fitness(&xs);
+        ctx.best.update(&xs, &ys);
+        ctx.replace_worst(xs, ys);
+    }
+}
@@ -0,0 +1,264 @@
+//! # Non-dominated Sorting Genetic Algorithm II
+//!
+//! <https://doi.org/10.1109/4235.996017>
+//!
+//! This method is designed for multi-objective problems, the domination is
+//! decided by [`Fitness::is_dominated_feasible()`]. The crowding distance is
+//! measured in the objective space (see [`Fitness::objective_values()`] and
+//! [`crowding_distance()`]), or the design variable space if the objective
+//! values are not available.
+//!
+//! This method require floating point power function.
+use crate::prelude::*;
+use alloc::vec::Vec;
+
+const DEF: Nsga2 = Nsga2 { cross: 0.9, mutate: 0.1, eta_c: 20., eta_m: 20. };
+
+/// Non-dominated Sorting Genetic Algorithm II settings.
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "clap", derive(clap::Args))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct Nsga2 {
+    /// Crossover rate
+    #[cfg_attr(feature = "clap", clap(long, default_value_t = DEF.cross))]
+    pub cross: f64,
+    /// Mutation rate of each variable
+    #[cfg_attr(feature = "clap", clap(long, default_value_t = DEF.mutate))]
+    pub mutate: f64,
+    /// Distribution index of the crossover
+    #[cfg_attr(feature = "clap", clap(long, default_value_t = DEF.eta_c))]
+    pub eta_c: f64,
+    /// Distribution index of the mutation
+    #[cfg_attr(feature = "clap", clap(long, default_value_t = DEF.eta_m))]
+    pub eta_m: f64,
+}
+
+impl Nsga2 {
+    /// Constant default value.
+    pub const fn new() -> Self {
+        DEF
+    }
+
+    impl_builders! {
+        /// Crossing probability.
+        fn cross(f64)
+        /// Mutation probability of each variable.
+        fn mutate(f64)
+        /// Distribution index of the simulated binary crossover.
+        fn eta_c(f64)
+        /// Distribution index of the polynomial mutation.
+        fn eta_m(f64)
+    }
+}
+
+impl Default for Nsga2 {
+    fn default() -> Self {
+        DEF
+    }
+}
+
+impl AlgCfg for Nsga2 {
+    type Algorithm<F: ObjFunc> = Method;
+    fn algorithm<F: ObjFunc>(self) -> Self::Algorithm<F> {
+        Method { nsga2: self, rank: Vec::new(), crowd: Vec::new() }
+    }
+    fn pop_num() -> usize {
+        100
+    }
+}
+
+/// Algorithm of the Non-dominated Sorting Genetic Algorithm II.
+#[derive(Clone)]
+pub struct Method {
+    nsga2: Nsga2,
+    rank: Vec<usize>,
+    crowd: Vec<f64>,
+}
+
+impl core::ops::Deref for Method {
+    type Target = Nsga2;
+
+    fn deref(&self) -> &Self::Target {
+        &self.nsga2
+    }
+}
+
+/// Fast non-dominated sorting, returns the indices of each front.
+fn non_dominated_sort<Y: Fitness>(ys: &[Y]) -> Vec<Vec<usize>> {
+    let n = ys.len();
+    let mut dominate = alloc::vec![Vec::new(); n];
+    let mut count = alloc::vec![0; n];
+    for i in 0..n {
+        for j in i + 1..n {
+            let ij = ys[i].is_dominated_feasible(&ys[j]);
+            let ji = ys[j].is_dominated_feasible(&ys[i]);
+            // Equal individuals are not dominated by each other
+            if ij && !ji {
+                dominate[i].push(j);
+                count[j] += 1;
+            } else if ji && !ij {
+                dominate[j].push(i);
+                count[i] += 1;
+            }
+        }
+    }
+    let mut fronts = Vec::new();
+    let mut front = (0..n).filter(|i| count[*i] == 0).collect::<Vec<_>>();
+    while !front.is_empty() {
+        let mut next = Vec::new();
+        for &i in &front {
+            for &j in &dominate[i] {
+                count[j] -= 1;
+                if count[j] == 0 {
+                    next.push(j);
+                }
+            }
+        }
+        fronts.push(front);
+        front = next;
+    }
+    fronts
+}
+
+impl Method {
+    // Select the better individual by (rank, crowding distance)
+    fn tournament(&self, rng: &mut Rng) -> usize {
+        // The only individual wins
+        if self.rank.len() < 2 {
+            return 0;
+        }
+        let [a, b] = rng.array(0..self.rank.len());
+        if self.rank[a] < self.rank[b]
+            || self.rank[a] == self.rank[b] && self.crowd[a] > self.crowd[b]
+        {
+            a
+        } else {
+            b
+        }
+    }
+
+    // Simulated binary crossover
+    fn crossover<F: ObjFunc>(
+        &self,
+        ctx: &Ctx<F>,
+        rng: &mut Rng,
+        a: usize,
+        b: usize,
+    ) -> [Vec<f64>; 2] {
+        let mut c1 = ctx.pool[a].clone();
+        let mut c2 = ctx.pool[b].clone();
+        if !rng.maybe(self.cross) {
+            return [c1, c2];
+        }
+        for s in 0..ctx.dim() {
+            if !rng.maybe(0.5) {
+                continue;
+            }
+            let u = rng.rand();
+            let beta = if u <= 0.5 {
+                (2. * u).powf((self.eta_c + 1.).recip())
+            } else {
+                (0.5 / (1. - u)).powf((self.eta_c + 1.).recip())
+            };
+            let (x1, x2) = (c1[s], c2[s]);
+            c1[s] = ctx.clamp(s, 0.5 * ((1. + beta) * x1 + (1. - beta) * x2));
+            c2[s] = ctx.clamp(s, 0.5 * ((1. - beta) * x1 + (1. + beta) * x2));
+        }
+        [c1, c2]
+    }
+
+    // Polynomial mutation
+    fn mutation<F: ObjFunc>(&self, ctx: &Ctx<F>, rng: &mut Rng, xs: &mut [f64]) {
+        for (s, x) in xs.iter_mut().enumerate() {
+            if !rng.maybe(self.mutate) {
+                continue;
+            }
+            let u = rng.rand();
+            let delta = if u < 0.5 {
+                (2. * u).powf((self.eta_m + 1.).recip()) - 1.
+            } else {
+                1. - (2. * (1. - u)).powf((self.eta_m + 1.).recip())
+            };
+            *x = ctx.clamp(s, *x + delta * ctx.bound_width(s));
+        }
+    }
+
+    // Rank and crowding distance of the pool
+    fn assign<F: ObjFunc>(&mut self, ctx: &Ctx<F>) {
+        self.rank = alloc::vec![0; ctx.pop_num()];
+        self.crowd = alloc::vec![0.; ctx.pop_num()];
+        let points = crowding_points(&ctx.pool, &ctx.pool_y);
+        for (rank, front) in non_dominated_sort(&ctx.pool_y).into_iter().enumerate() {
+            let xs = front
+                .iter()
+                .map(|i| points[*i].as_slice())
+                .collect::<Vec<_>>();
+            for (i, d) in front.into_iter().zip(crowding_distance(&xs)) {
+                self.rank[i] = rank;
+                self.crowd[i] = d;
+            }
+        }
+    }
+}
+
+impl<F: ObjFunc> Algorithm<F> for Method {
+    fn init(&mut self, ctx: &mut Ctx<F>, _: &mut Rng) {
+        self.assign(ctx);
+    }
+
+    fn generation(&mut self, ctx: &mut Ctx<F>, rng: &mut Rng) {
+        let pop_num = ctx.pop_num();
+        // The pool may be resized by the solver, see `SolverBuilder::pop_schedule()`
+        if self.rank.len() != pop_num {
+            self.assign(ctx);
+        }
+        // Offspring
+        let mut children = Vec::with_capacity(pop_num + 1);
+        while children.len() < pop_num {
+            let a = self.tournament(rng);
+            let b = self.tournament(rng);
+            for mut xs in self.crossover(ctx, rng, a, b) {
+                self.mutation(ctx, rng, &mut xs);
+                children.push(xs);
+            }
+        }
+        children.truncate(pop_num);
+        let children_y = ctx.fitness_batch(&children);
+        ctx.best.update_all(&children, &children_y);
+        // Environmental selection
+        let mut pool = core::mem::take(&mut ctx.pool);
+        let mut pool_y = core::mem::take(&mut ctx.pool_y);
+        pool.extend(children);
+        pool_y.extend(children_y);
+        let mut selected = Vec::with_capacity(pop_num);
+        for mut front in non_dominated_sort(&pool_y) {
+            if selected.len() + front.len() > pop_num {
+                let points = (front.iter())
+                    .map(|i| match pool_y[*i].objective_values() {
+                        Some(ys) => ys,
+                        None => pool[*i].clone(),
+                    })
+                    .collect::<Vec<_>>();
+                let xs = points.iter().map(Vec::as_slice).collect::<Vec<_>>();
+                let d = crowding_distance(&xs);
+                let mut ind = (0..front.len()).collect::<Vec<_>>();
+                ind.sort_by(|i, j| d[*j].total_cmp(&d[*i]));
+                front = ind.into_iter().map(|i| front[i]).collect();
+                front.truncate(pop_num - selected.len());
+            }
+            selected.extend(front);
+            if selected.len() == pop_num {
+                break;
+            }
+        }
+        let mut pool = pool.into_iter().map(Some).collect::<Vec<_>>();
+        let mut pool_y = pool_y.into_iter().map(Some).collect::<Vec<_>>();
+        ctx.pool = selected.iter().map(|i| pool[*i].take().unwrap()).collect();
+        ctx.pool_y = selected
+            .iter()
+            .map(|i| pool_y[*i].take().unwrap())
+            .collect();
+        self.assign(ctx);
+    }
+}
@@ -3,14 +3,74 @@
 //! <https://en.wikipedia.org/wiki/Particle_swarm_optimization>
 use crate::prelude::*;
 use alloc::vec::Vec;
+use core::{fmt, str::FromStr};
 
-const DEF: Pso = Pso { cognition: 2.05, social: 2.05, velocity: 1.3 };
+const DEF: Pso = Pso {
+    cognition: 2.05,
+    social: 2.05,
+    w_start: 0.9,
+    w_end: 0.4,
+    v_max: None,
+    topology: Topology::Global,
+};
+
+/// Neighborhood topology of the particles.
+///
+/// In the command line, the topology is written as `global` or `ring:<k>`.
+#[derive(Default, Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Topology {
+    /// Each particle is influenced by the global best.
+    #[default]
+    Global,
+    /// Each particle is influenced by the best among its `k` neighbors on each
+    /// side of the index ring.
+    ///
+    /// The information spreads slower than the global topology, so the
+    /// premature convergence on multimodal problems is reduced.
+    Ring {
+        /// Number of neighbors on each side
+        k: usize,
+    },
+}
+
+impl fmt::Display for Topology {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Global => write!(f, "global"),
+            Self::Ring { k } => write!(f, "ring:{k}"),
+        }
+    }
+}
+
+impl FromStr for Topology {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            None if s == "global" => Ok(Self::Global),
+            Some(("ring", k)) => match k.parse() {
+                Ok(k) => Ok(Self::Ring { k }),
+                Err(_) => Err("invalid neighbor number"),
+            },
+            _ => Err("expect \"global\" or \"ring:<k>\""),
+        }
+    }
+}
 
 /// Particle Swarm Optimization settings.
+///
+/// The inertia weight is decreased linearly from `w_start` to `w_end` over
+/// the run, which requires the maximum generation number (see
+/// [`Ctx::progress()`]). If it is unknown, the weight reaches `w_end` at the
+/// 100th generation.
+///
+/// The deprecated `velocity` field is still accepted by the deserialization,
+/// which is a fixed inertia weight, see [`Pso::velocity()`].
 #[derive(Clone, PartialEq)]
 #[cfg_attr(feature = "clap", derive(clap::Args))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-#[cfg_attr(feature = "serde", serde(default))]
+#[cfg_attr(feature = "serde", serde(from = "PsoCompat"))]
 pub struct Pso {
     /// Cognition factor
     #[cfg_attr(feature = "clap", clap(long, default_value_t = DEF.cognition))]
@@ -18,9 +78,18 @@ pub struct Pso {
     /// Social factor
     #[cfg_attr(feature = "clap", clap(long, default_value_t = DEF.social))]
     pub social: f64,
-    /// Velocity factor
-    #[cfg_attr(feature = "clap", clap(long, default_value_t = DEF.velocity))]
-    pub velocity: f64,
+    /// Initial inertia weight
+    #[cfg_attr(feature = "clap", clap(long, default_value_t = DEF.w_start))]
+    pub w_start: f64,
+    /// Final inertia weight
+    #[cfg_attr(feature = "clap", clap(long, default_value_t = DEF.w_end))]
+    pub w_end: f64,
+    /// Maximum velocity, the ratio of the bound width
+    #[cfg_attr(feature = "clap", clap(long))]
+    pub v_max: Option<f64>,
+    /// Neighborhood topology
+    #[cfg_attr(feature = "clap", clap(long, default_value_t = DEF.topology))]
+    pub topology: Topology,
 }
 
 impl Pso {
@@ -34,8 +103,25 @@ impl Pso {
         fn cognition(f64)
         /// Social factor.
         fn social(f64)
-        /// Moving velocity.
-        fn velocity(f64)
+        /// Initial inertia weight.
+        fn w_start(f64)
+        /// Final inertia weight.
+        fn w_end(f64)
+        /// Neighborhood topology.
+        fn topology(Topology)
+    }
+
+    /// Fixed inertia weight.
+    pub fn velocity(self, w: f64) -> Self {
+        Self { w_start: w, w_end: w, ..self }
+    }
+
+    /// Maximum velocity, the ratio of the bound width.
+    ///
+    /// For example, `0.2` means the particle can move 20% of the bound width
+    /// of each variable in a generation.
+    pub fn v_max(self, v_max: f64) -> Self {
+        Self { v_max: Some(v_max), ..self }
     }
 }
 
@@ -45,18 +131,82 @@ impl Default for Pso {
     }
 }
 
+// The settings with the deprecated velocity field
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+#[serde(default)]
+struct PsoCompat {
+    cognition: f64,
+    social: f64,
+    w_start: f64,
+    w_end: f64,
+    v_max: Option<f64>,
+    topology: Topology,
+    #[serde(deserialize_with = "velocity")]
+    velocity: Option<f64>,
+}
+
+// The deprecated field is a number
+#[cfg(feature = "serde")]
+fn velocity<'de, D: serde::Deserializer<'de>>(de: D) -> Result<Option<f64>, D::Error> {
+    serde::Deserialize::deserialize(de).map(Some)
+}
+
+#[cfg(feature = "serde")]
+impl Default for PsoCompat {
+    fn default() -> Self {
+        let Pso { cognition, social, w_start, w_end, v_max, topology } = DEF;
+        Self {
+            cognition,
+            social,
+            w_start,
+            w_end,
+            v_max,
+            topology,
+            velocity: None,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<PsoCompat> for Pso {
+    fn from(pso: PsoCompat) -> Self {
+        let PsoCompat {
+            cognition,
+            social,
+            w_start,
+            w_end,
+            v_max,
+            topology,
+            velocity,
+        } = pso;
+        let pso = Self { cognition, social, w_start, w_end, v_max, topology };
+        match velocity {
+            Some(w) => pso.velocity(w),
+            None => pso,
+        }
+    }
+}
+
 impl AlgCfg for Pso {
     type Algorithm<F: ObjFunc> = Method<F::Ys>;
     fn algorithm<F: ObjFunc>(self) -> Self::Algorithm<F> {
-        Method { pso: self, past: Vec::new(), past_y: Vec::new() }
+        Method {
+            pso: self,
+            past: Vec::new(),
+            past_y: Vec::new(),
+            vel: Vec::new(),
+        }
     }
 }
 
 /// Algorithm of the Particle Swarm Optimization.
+#[derive(Clone)]
 pub struct Method<Y: Fitness> {
     pso: Pso,
     past: Vec<Vec<f64>>,
     past_y: Vec<Y>,
+    vel: Vec<Vec<f64>>,
 }
 
 impl<Y: Fitness> core::ops::Deref for Method<Y> {
@@ -67,39 +217,90 @@ impl<Y: Fitness> core::ops::Deref for Method<Y> {
     }
 }
 
+impl<Y: Fitness> Method<Y> {
+    // The best personal record among the neighbors of each particle
+    fn ring_best(&self, k: usize) -> Vec<Vec<f64>> {
+        let n = self.past.len();
+        let k = k.min(n / 2);
+        (0..n)
+            .map(|i| {
+                let best = ((n + i - k)..=(n + i + k))
+                    .map(|j| j % n)
+                    .reduce(|a, b| {
+                        if self.past_y[b].is_dominated_feasible(&self.past_y[a]) {
+                            b
+                        } else {
+                            a
+                        }
+                    })
+                    .unwrap();
+                self.past[best].clone()
+            })
+            .collect()
+    }
+}
+
 impl<F: ObjFunc> Algorithm<F> for Method<F::Ys> {
     fn init(&mut self, ctx: &mut Ctx<F>, _: &mut Rng) {
         self.past = ctx.pool.clone();
         self.past_y = ctx.pool_y.clone();
+        self.vel = alloc::vec![alloc::vec![0.; ctx.dim()]; ctx.pop_num()];
     }
 
     fn generation(&mut self, ctx: &mut Ctx<F>, rng: &mut Rng) {
+        // The pool may be resized by the solver, see `SolverBuilder::pop_schedule()`
+        if self.vel.len() != ctx.pop_num() {
+            self.init(ctx, rng);
+        }
         let rng = rng.stream(ctx.pop_num());
         let cognition = self.cognition;
         let social = self.social;
-        let velocity = self.velocity;
+        let w = Schedule::Linear { from: self.w_start, to: self.w_end };
+        let w = w.value(ctx.gen, ctx.max_gen());
+        let v_max = self.v_max;
+        let topology = self.topology;
+        let local = match topology {
+            Topology::Global => Vec::new(),
+            Topology::Ring { k } => self.ring_best(k),
+        };
         #[cfg(not(feature = "rayon"))]
         let iter = rng.into_iter();
         #[cfg(feature = "rayon")]
         let iter = rng.into_par_iter();
-        iter.zip(&mut ctx.pool)
-            .zip(&mut ctx.pool_y)
+        let mut pool = core::mem::take(&mut ctx.pool);
+        let mut pool_y = core::mem::take(&mut ctx.pool_y);
+        iter.enumerate()
+            .zip(&mut pool)
+            .zip(&mut pool_y)
             .zip(&mut self.past)
             .zip(&mut self.past_y)
-            .for_each(|((((mut rng, xs), ys), past), past_y)| {
+            .zip(&mut self.vel)
+            .for_each(|((((((i, mut rng), xs), ys), past), past_y), vel)| {
                 let alpha = rng.ub(cognition);
                 let beta = rng.ub(social);
-                let best = ctx.best.sample_xs(&mut rng);
-                for s in 0..ctx.func.dim() {
-                    let v = velocity * xs[s] + alpha * (past[s] - xs[s]) + beta * (best[s] - xs[s]);
-                    xs[s] = ctx.func.clamp(s, v);
+                let best = match topology {
+                    Topology::Global => ctx.best.sample_xs(&mut rng),
+                    Topology::Ring { .. } => &local[i],
+                };
+                for s in 0..ctx.dim() {
+                    let v = w * vel[s] + alpha * (past[s] - xs[s]) + beta * (best[s] - xs[s]);
+                    vel[s] = match v_max {
+                        Some(v_max) => {
+                            let v_max = v_max * ctx.bound_width(s);
+                            v.clamp(-v_max, v_max)
+                        }
+                        None => v,
+                    };
+                    xs[s] = ctx.clamp(s, xs[s] + vel[s]);
                 }
-                *ys = ctx.func.fitness(xs);
-                if ys.is_dominated(&*past_y) {
+                *ys = ctx.fitness(xs);
+                if ys.is_dominated_feasible(&*past_y) {
                     *past = xs.clone();
                     *past_y = ys.clone();
                 }
             });
+        ctx.pool = pool;
+        ctx.pool_y = pool_y;
         ctx.find_best();
     }
 }
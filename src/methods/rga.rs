@@ -6,13 +6,78 @@
 //!
 //! This method require floating point power function.
 use crate::prelude::*;
-use alloc::vec::Vec;
-use core::iter::zip;
+use alloc::{collections::VecDeque, vec::Vec};
+use core::{fmt, iter::zip, str::FromStr};
 
-/// Algorithm of the Real-coded Genetic Algorithm.
-pub type Method = Rga;
+const DEF: Rga = Rga {
+    cross: 0.95,
+    mutate: 0.05,
+    win: 0.95,
+    delta: 5.,
+    tournament: 2,
+    elitism: 1,
+    crossover_op: Crossover::Arithmetic,
+    adapt_step: false,
+};
+/// Window size (generations) of the success rate in the 1/5th rule.
+const WINDOW: usize = 10;
+
+/// Crossover operator of the Real-coded Genetic Algorithm.
+///
+/// In the command line, the operator is written as `arithmetic`, `sbx:<eta>`,
+/// or `blx:<alpha>`.
+#[derive(Default, Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Crossover {
+    /// Generate three children `0.5a + 0.5b`, `1.5a - 0.5b`, and `-0.5a +
+    /// 1.5b`, and then keep the best two.
+    #[default]
+    Arithmetic,
+    /// Simulated binary crossover.
+    ///
+    /// The larger `eta` generates the children closer to the parents.
+    Sbx {
+        /// Distribution index
+        eta: f64,
+    },
+    /// Blend crossover (BLX-α).
+    ///
+    /// The children are sampled uniformly from the range of the parents,
+    /// extended by `alpha` times the distance on both sides.
+    Blx {
+        /// Extension ratio
+        alpha: f64,
+    },
+}
 
-const DEF: Rga = Rga { cross: 0.95, mutate: 0.05, win: 0.95, delta: 5. };
+impl fmt::Display for Crossover {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Arithmetic => write!(f, "arithmetic"),
+            Self::Sbx { eta } => write!(f, "sbx:{eta}"),
+            Self::Blx { alpha } => write!(f, "blx:{alpha}"),
+        }
+    }
+}
+
+impl FromStr for Crossover {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            None if s == "arithmetic" => Ok(Self::Arithmetic),
+            Some(("sbx", eta)) => match eta.parse() {
+                Ok(eta) => Ok(Self::Sbx { eta }),
+                Err(_) => Err("invalid distribution index"),
+            },
+            Some(("blx", alpha)) => match alpha.parse() {
+                Ok(alpha) => Ok(Self::Blx { alpha }),
+                Err(_) => Err("invalid extension ratio"),
+            },
+            _ => Err("expect \"arithmetic\", \"sbx:<eta>\" or \"blx:<alpha>\""),
+        }
+    }
+}
 
 /// Real-coded Genetic Algorithm settings.
 #[derive(Clone, PartialEq)]
@@ -32,6 +97,18 @@ pub struct Rga {
     /// Delta
     #[cfg_attr(feature = "clap", clap(long, default_value_t = DEF.delta))]
     pub delta: f64,
+    /// Tournament size
+    #[cfg_attr(feature = "clap", clap(long, default_value_t = DEF.tournament))]
+    pub tournament: usize,
+    /// Number of elites
+    #[cfg_attr(feature = "clap", clap(long, default_value_t = DEF.elitism))]
+    pub elitism: usize,
+    /// Crossover operator
+    #[cfg_attr(feature = "clap", clap(long, default_value_t = DEF.crossover_op))]
+    pub crossover_op: Crossover,
+    /// Adapt the mutation step by the 1/5th success rule
+    #[cfg_attr(feature = "clap", clap(long))]
+    pub adapt_step: bool,
 }
 
 impl Rga {
@@ -49,6 +126,28 @@ impl Rga {
         fn win(f64)
         /// Delta factor.
         fn delta(f64)
+        /// Tournament size of the selection, the larger size increases the
+        /// selection pressure.
+        ///
+        /// The size is clamped to `1..=pop_num`, where the size of one is the
+        /// random selection.
+        fn tournament(usize)
+        /// Number of the best individuals preserved across the generation.
+        ///
+        /// The first elite is sampled from the best container (such as a
+        /// random member of the Pareto front) if the design is missing from
+        /// the pool, and the others are the best individuals of the pool.
+        fn elitism(usize)
+        /// Crossover operator.
+        fn crossover_op(Crossover)
+        /// Adapt the mutation step by Rechenberg's 1/5th success rule.
+        ///
+        /// The mutation is successful if the mutated individual dominates the
+        /// original one. The step is enlarged if the success rate of the
+        /// recent generations is greater than 1/5, otherwise it is shrunk.
+        /// This option replaces the generation-based decay of
+        /// [`Rga::delta`].
+        fn adapt_step(bool)
     }
 }
 
@@ -61,31 +160,168 @@ impl Default for Rga {
 impl AlgCfg for Rga {
     type Algorithm<F: ObjFunc> = Method;
     fn algorithm<F: ObjFunc>(self) -> Self::Algorithm<F> {
-        self
+        Method { rga: self, step: 1., history: VecDeque::new() }
     }
     fn pop_num() -> usize {
         500
     }
 }
 
+/// Algorithm of the Real-coded Genetic Algorithm.
+#[derive(Clone)]
+pub struct Method {
+    rga: Rga,
+    // Mutation step scale of the 1/5th rule, in `(0, 1]`
+    step: f64,
+    // Number of the successful and total mutations of the recent generations
+    history: VecDeque<[u32; 2]>,
+}
+
+impl core::ops::Deref for Method {
+    type Target = Rga;
+
+    fn deref(&self) -> &Self::Target {
+        &self.rga
+    }
+}
+
 impl Method {
     fn get_delta(&self, gen: u64, rng: &mut Rng, y: f64) -> f64 {
+        if self.adapt_step {
+            return rng.rand() * y * self.step;
+        }
         let r = if gen < 100 { gen as f64 / 100. } else { 1. };
         rng.rand() * y * (1. - r).powf(self.delta)
     }
+
+    // The 1/5th success rule
+    fn adapt(&mut self, record: [u32; 2]) {
+        if self.history.len() == WINDOW {
+            self.history.pop_front();
+        }
+        self.history.push_back(record);
+        let [success, total] =
+            (self.history.iter()).fold([0, 0], |[s, t], [s1, t1]| [s + s1, t + t1]);
+        if total == 0 {
+            return;
+        }
+        const FACTOR: f64 = 0.85;
+        if success as f64 / total as f64 > 0.2 {
+            self.step = (self.step / FACTOR).min(1.);
+        } else {
+            self.step = (self.step * FACTOR).max(f64::EPSILON);
+        }
+    }
+
+    // Two children of the pair `i` and `i + 1` with their fitness values
+    fn children<F: ObjFunc>(
+        &self,
+        ctx: &Ctx<F>,
+        rng: &mut Rng,
+        i: usize,
+    ) -> Vec<(Vec<f64>, F::Ys)> {
+        match self.crossover_op {
+            Crossover::Arithmetic => arithmetic(ctx, rng, i),
+            Crossover::Sbx { eta } => pair(ctx, i, |a, b| {
+                let u = rng.rand();
+                let beta = if u <= 0.5 {
+                    (2. * u).powf((eta + 1.).recip())
+                } else {
+                    (0.5 / (1. - u)).powf((eta + 1.).recip())
+                };
+                [
+                    0.5 * ((1. + beta) * a + (1. - beta) * b),
+                    0.5 * ((1. - beta) * a + (1. + beta) * b),
+                ]
+            }),
+            Crossover::Blx { alpha } => pair(ctx, i, |a, b| {
+                let d = alpha * (a - b).abs();
+                let range = a.min(b) - d..=a.max(b) + d;
+                [rng.range(range.clone()), rng.range(range)]
+            }),
+        }
+    }
+}
+
+// Three children of the pair `i` and `i + 1` by the arithmetic crossover, keep
+// the best two
+fn arithmetic<F: ObjFunc>(ctx: &Ctx<F>, rng: &mut Rng, i: usize) -> Vec<(Vec<f64>, F::Ys)> {
+    let children = (rng.stream(3).into_iter().enumerate())
+        .map(|(id, mut rng)| {
+            zip(ctx.bound(), zip(&ctx.pool[i], &ctx.pool[i + 1]))
+                .enumerate()
+                .map(|(s, (&[min, max], (a, b)))| {
+                    let v = match id {
+                        0 => 0.5 * (a + b),
+                        1 => 1.5 * a - 0.5 * b,
+                        _ => -0.5 * a + 1.5 * b,
+                    };
+                    ctx.quantize(s, rng.clamp(v, min..=max))
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    let children_y = ctx.fitness_batch(&children);
+    let mut ret = zip(children, children_y).collect::<Vec<_>>();
+    // Stable sort, the tied candidates keep their generated order
+    ret.sort_by(|(_, a), (_, b)| cmp_fit(a, b));
+    ret.truncate(2);
+    ret
+}
+
+// Two children of the pair `i` and `i + 1` by a variable-wise operator, such as
+// SBX or BLX-α
+fn pair<F: ObjFunc>(
+    ctx: &Ctx<F>,
+    i: usize,
+    mut op: impl FnMut(f64, f64) -> [f64; 2],
+) -> Vec<(Vec<f64>, F::Ys)> {
+    let mut c1 = ctx.pool[i].clone();
+    let mut c2 = ctx.pool[i + 1].clone();
+    for (s, (v1, v2)) in zip(&mut c1, &mut c2).enumerate() {
+        let [a, b] = op(*v1, *v2);
+        *v1 = ctx.quantize(s, ctx.clamp(s, a));
+        *v2 = ctx.quantize(s, ctx.clamp(s, b));
+    }
+    let children = alloc::vec![c1, c2];
+    let children_y = ctx.fitness_batch(&children);
+    zip(children, children_y).collect()
 }
 
 impl<F: ObjFunc> Algorithm<F> for Method {
     fn generation(&mut self, ctx: &mut Ctx<F>, rng: &mut Rng) {
+        let mut elites = (ctx.best_indices(self.elitism).into_iter())
+            .map(|i| (ctx.pool[i].clone(), ctx.pool_y[i].clone()))
+            .collect::<Vec<_>>();
+        if self.elitism > 0 {
+            // Reinsert the archived design, such as a member of the Pareto front
+            let (xs, ys) = ctx.best.sample(rng);
+            if !ctx.pool.iter().any(|p| p == xs) {
+                elites.insert(0, (xs.to_vec(), ys.clone()));
+                elites.truncate(self.elitism);
+            }
+        }
         // Select
         let mut pool = ctx.pool.clone();
         let mut pool_y = ctx.pool_y.clone();
-        for (xs, ys) in zip(&mut pool, &mut pool_y) {
-            let [a, b] = rng.array(0..ctx.pop_num());
-            let i = if ctx.pool_y[a].is_dominated(&ctx.pool_y[b]) {
+        let pick = |a: usize, b: usize| {
+            if ctx.pool_y[a].is_dominated_feasible(&ctx.pool_y[b]) {
                 a
             } else {
                 b
+            }
+        };
+        let k = self.tournament.clamp(1, ctx.pop_num());
+        for (xs, ys) in zip(&mut pool, &mut pool_y) {
+            let i = match k {
+                1 => rng.ub(ctx.pop_num()),
+                2 => {
+                    let [a, b] = rng.array(0..ctx.pop_num());
+                    pick(a, b)
+                }
+                k => (rng.subset(ctx.pop_num(), k).into_iter())
+                    .reduce(pick)
+                    .unwrap(),
             };
             if rng.maybe(self.win) {
                 *xs = ctx.pool[i].clone();
@@ -94,57 +330,44 @@ impl<F: ObjFunc> Algorithm<F> for Method {
         }
         ctx.pool = pool;
         ctx.pool_y = pool_y;
-        {
-            let i = rng.ub(ctx.pop_num());
-            let (xs, ys) = ctx.best.sample(rng);
-            ctx.set_from(i, xs.to_vec(), ys.clone());
-        }
         // Crossover
         for i in (0..ctx.pop_num() - 1).step_by(2) {
             if !rng.maybe(self.cross) {
                 continue;
             }
-            #[cfg(not(feature = "rayon"))]
-            let iter = rng.stream(3).into_iter();
-            #[cfg(feature = "rayon")]
-            let iter = rng.stream(3).into_par_iter();
-            let mut ret: [_; 3] = iter
-                .enumerate()
-                .map(|(id, mut rng)| {
-                    let xs = zip(ctx.bound(), zip(&ctx.pool[i], &ctx.pool[i + 1]))
-                        .map(|(&[min, max], (a, b))| {
-                            let v = match id {
-                                0 => 0.5 * (a + b),
-                                1 => 1.5 * a - 0.5 * b,
-                                _ => -0.5 * a + 1.5 * b,
-                            };
-                            rng.clamp(v, min..=max)
-                        })
-                        .collect::<Vec<_>>();
-                    let ys = ctx.fitness(&xs);
-                    (ys, xs)
-                })
-                .collect::<Vec<_>>()
-                .try_into()
-                .unwrap_or_else(|_| unreachable!());
-            ret.sort_unstable_by(|(a, _), (b, _)| a.eval().partial_cmp(&b.eval()).unwrap());
-            let [(t1_f, t1_x), (t2_f, t2_x), ..] = ret;
-            ctx.set_from(i, t1_x, t1_f);
-            ctx.set_from(i + 1, t2_x, t2_f);
+            for (j, (xs, ys)) in self.children(ctx, rng, i).into_iter().enumerate() {
+                ctx.set_from(i + j, xs, ys);
+            }
         }
         // Mutate
         let dim = ctx.dim();
-        for (xs, ys) in zip(&mut ctx.pool, &mut ctx.pool_y) {
+        let mut record = [0; 2];
+        for i in 0..ctx.pop_num() {
             if !rng.maybe(self.mutate) {
                 continue;
             }
             let s = rng.ub(dim);
+            let xs = &mut ctx.pool[i];
             if rng.maybe(0.5) {
                 xs[s] += self.get_delta(ctx.gen, rng, ctx.func.ub(s) - xs[s]);
             } else {
                 xs[s] -= self.get_delta(ctx.gen, rng, xs[s] - ctx.func.lb(s));
             }
-            *ys = ctx.func.fitness(xs);
+            xs[s] = ctx.func.quantize(s, xs[s]);
+            let ys = ctx.fitness(&ctx.pool[i]);
+            if ys.is_dominated_feasible(&ctx.pool_y[i]) {
+                record[0] += 1;
+            }
+            record[1] += 1;
+            ctx.pool_y[i] = ys;
+        }
+        if self.adapt_step {
+            self.adapt(record);
+        }
+        // Replace the worst individuals with the elites
+        let worst = ctx.rank().into_iter().rev();
+        for (i, (xs, ys)) in zip(worst, elites) {
+            ctx.set_from(i, xs, ys);
         }
         ctx.find_best();
     }
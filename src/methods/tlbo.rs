@@ -32,7 +32,7 @@ impl AlgCfg for Tlbo {
 
 fn register<F: ObjFunc>(ctx: &mut Ctx<F>, i: usize, student: Vec<f64>) {
     let f_new = ctx.fitness(&student);
-    if f_new.is_dominated(&ctx.pool_y[i]) {
+    if f_new.is_dominated_feasible(&ctx.pool_y[i]) {
         ctx.set_from(i, student, f_new);
         ctx.best.update(&ctx.pool[i], &ctx.pool_y[i]);
     }
@@ -67,7 +67,7 @@ fn learning<F: ObjFunc>(ctx: &mut Ctx<F>, rng: &mut Rng, i: usize) {
     };
     let student = zip(ctx.bound(), zip(&ctx.pool[i], &ctx.pool[j]))
         .map(|(&[min, max], (a, b))| {
-            let diff = if ctx.pool_y[j].is_dominated(&ctx.pool_y[i]) {
+            let diff = if ctx.pool_y[j].is_dominated_feasible(&ctx.pool_y[i]) {
                 a - b
             } else {
                 b - a
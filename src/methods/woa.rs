@@ -0,0 +1,96 @@
+//! # Whale Optimization Algorithm
+//!
+//! <https://doi.org/10.1016/j.advengsoft.2016.01.008>
+//!
+//! This method require exponential and trigonometric functions.
+use crate::prelude::*;
+use core::f64::consts::TAU;
+
+/// Algorithm of the Whale Optimization Algorithm.
+pub type Method = Woa;
+
+const DEF: Woa = Woa { spiral: 1. };
+
+/// Whale Optimization Algorithm settings.
+///
+/// The coefficient `a` is decreased linearly from 2 to 0 over the run, which
+/// requires the maximum generation number (see [`Ctx::progress()`]). If it is
+/// unknown, the coefficient reaches zero at the 100th generation.
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "clap", derive(clap::Args))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct Woa {
+    /// Spiral shape constant
+    #[cfg_attr(feature = "clap", clap(long, default_value_t = DEF.spiral))]
+    pub spiral: f64,
+}
+
+impl Woa {
+    /// Constant default value.
+    pub const fn new() -> Self {
+        DEF
+    }
+
+    impl_builders! {
+        /// Spiral shape constant of the bubble-net attacking.
+        fn spiral(f64)
+    }
+}
+
+impl Default for Woa {
+    fn default() -> Self {
+        DEF
+    }
+}
+
+impl AlgCfg for Woa {
+    type Algorithm<F: ObjFunc> = Method;
+    fn algorithm<F: ObjFunc>(self) -> Self::Algorithm<F> {
+        self
+    }
+}
+
+impl<F: ObjFunc> Algorithm<F> for Method {
+    fn generation(&mut self, ctx: &mut Ctx<F>, rng: &mut Rng) {
+        let a = Schedule::Linear { from: 2., to: 0. }.value(ctx.gen, ctx.max_gen());
+        let mut pool = ctx.pool.clone();
+        let mut pool_y = ctx.pool_y.clone();
+        let rng = rng.stream(ctx.pop_num());
+        #[cfg(not(feature = "rayon"))]
+        let iter = rng.into_iter();
+        #[cfg(feature = "rayon")]
+        let iter = rng.into_par_iter();
+        iter.zip(&mut pool)
+            .zip(&mut pool_y)
+            .for_each(|((mut rng, xs), ys)| {
+                let best = ctx.best.sample_xs(&mut rng);
+                if rng.maybe(0.5) {
+                    // Bubble-net attacking
+                    let l = rng.range(-1.0..1.);
+                    let k = (self.spiral * l).exp() * (TAU * l).cos();
+                    for s in 0..ctx.dim() {
+                        let d = (best[s] - xs[s]).abs();
+                        xs[s] = ctx.clamp(s, d * k + best[s]);
+                    }
+                } else {
+                    // Encircling prey or searching for prey
+                    let a = 2. * a * rng.rand() - a;
+                    let c = 2. * rng.rand();
+                    let target = if a.abs() < 1. {
+                        best
+                    } else {
+                        &ctx.pool[rng.ub(ctx.pop_num())]
+                    };
+                    for s in 0..ctx.dim() {
+                        let d = (c * target[s] - xs[s]).abs();
+                        xs[s] = ctx.clamp(s, target[s] - a * d);
+                    }
+                }
+                *ys = ctx.fitness(xs);
+            });
+        ctx.pool = pool;
+        ctx.pool_y = pool_y;
+        ctx.find_best();
+    }
+}
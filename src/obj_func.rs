@@ -1,4 +1,5 @@
 use crate::prelude::*;
+use alloc::vec::Vec;
 
 /// A problem is well bounded.
 ///
@@ -28,6 +29,30 @@ pub trait Bounded: MaybeParallel {
         max - min
     }
 
+    /// Get the middle point of the upper bound and the lower bound.
+    fn midpoint(&self, s: usize) -> f64 {
+        let [min, max] = self.bound_of(s);
+        0.5 * (min + max)
+    }
+
+    /// Iterate over the bounds of all variables in `[lower, upper]` form.
+    ///
+    /// ```
+    /// use metaheuristics_nature::Bounded;
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// let func = MyFunc::new();
+    /// let center = func
+    ///     .bounds_iter()
+    ///     .map(|[lb, ub]| 0.5 * (lb + ub))
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(center.len(), func.dim());
+    /// ```
+    #[inline]
+    fn bounds_iter(&self) -> core::iter::Copied<core::slice::Iter<'_, [f64; 2]>> {
+        self.bound().iter().copied()
+    }
+
     /// Get the upper bound and the lower bound as a range.
     ///
     /// The variable is constrain with lower <= x <= upper.
@@ -54,6 +79,84 @@ pub trait Bounded: MaybeParallel {
         let [min, max] = self.bound_of(s);
         v.clamp(min, max)
     }
+
+    /// Clamp all the design variables to their bounds in place.
+    ///
+    /// See also [`Bounded::clamped_vec()`] for the non-mutating version.
+    ///
+    /// ```
+    /// use metaheuristics_nature::Bounded;
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// let func = MyFunc::new();
+    /// let mut xs = [-60., 0., 30., 70.];
+    /// func.clamp_vec(&mut xs);
+    /// assert_eq!(xs, [-50., 0., 30., 50.]);
+    /// ```
+    fn clamp_vec(&self, xs: &mut [f64]) {
+        for (s, x) in xs.iter_mut().enumerate() {
+            *x = self.clamp(s, *x);
+        }
+    }
+
+    /// Get the design variables clamped to their bounds.
+    ///
+    /// See also [`Bounded::clamp_vec()`].
+    fn clamped_vec(&self, xs: &[f64]) -> Vec<f64> {
+        let mut xs = xs.to_vec();
+        self.clamp_vec(&mut xs);
+        xs
+    }
+
+    /// Get the granularity (step size) of the variable `s`, none for a
+    /// continuous variable.
+    ///
+    /// The valid values of a discrete variable are `lower + k * step`, where
+    /// `k` is an integer. The methods that respect this option (such as
+    /// [`Rga`]) store the quantized values in the pool, see
+    /// [`Bounded::quantize()`].
+    ///
+    /// The default is none for all variables.
+    #[inline]
+    fn granularity(&self, s: usize) -> Option<f64> {
+        let _ = s;
+        None
+    }
+
+    /// Round the value `v` to the nearest valid value of the variable `s` by
+    /// its granularity, and then clamp it to the bounds.
+    ///
+    /// The value is returned directly if the variable is continuous.
+    fn quantize(&self, s: usize, v: f64) -> f64 {
+        match self.granularity(s) {
+            Some(step) => {
+                let lb = self.lb(s);
+                self.clamp(s, lb + ((v - lb) / step).round() * step)
+            }
+            None => v,
+        }
+    }
+}
+
+/// The progress information of the algorithm.
+///
+/// Passed to [`ObjFunc::fitness_ctx()`] by the solver, which is updated at the
+/// beginning of each generation.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct Progress<E> {
+    /// Generation number, zero in the initialization
+    pub gen: u64,
+    /// Best evaluation value, none in the initialization
+    pub best_eval: Option<E>,
+    /// Progress ratio in `0..=1`, see [`Ctx::progress()`]
+    pub ratio: Option<f64>,
+}
+
+impl<E> Default for Progress<E> {
+    fn default() -> Self {
+        Self { gen: 0, best_eval: None, ratio: None }
+    }
 }
 
 /// A trait for the objective function.
@@ -129,8 +232,111 @@ pub trait ObjFunc: Bounded {
     /// # Adaptive Value
     ///
     /// Sometimes a value that adjusts with converge states can help to restrict
-    /// the searching. The adaptive value can be set by hiding its mutability
-    /// with [`std::cell::Cell`] but not recommended. Please use the adaptive
-    /// value from the algorithm, not from the objective function.
+    /// the searching. Please implement [`ObjFunc::fitness_ctx()`] to get the
+    /// progress of the algorithm, instead of hiding the mutability with
+    /// [`std::cell::Cell`].
     fn fitness(&self, xs: &[f64]) -> Self::Ys;
+
+    /// Return fitness with the progress of the algorithm.
+    ///
+    /// The solver calls this method instead of [`ObjFunc::fitness()`], so the
+    /// penalty can be scaled by the generation. By default, it calls
+    /// [`ObjFunc::fitness()`] directly.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{Bounded, ObjFunc, Progress};
+    ///
+    /// struct MyFunc;
+    ///
+    /// impl Bounded for MyFunc {
+    ///     fn bound(&self) -> &[[f64; 2]] {
+    ///         &[[0., 50.]; 2]
+    ///     }
+    /// }
+    ///
+    /// impl ObjFunc for MyFunc {
+    ///     type Ys = f64;
+    ///
+    ///     fn fitness(&self, x: &[f64]) -> Self::Ys {
+    ///         x[0] * x[0] + x[1] * x[1]
+    ///     }
+    ///
+    ///     fn fitness_ctx(&self, x: &[f64], progress: &Progress<f64>) -> Self::Ys {
+    ///         // The penalty of `x0 + x1 < 10` is increased over the run
+    ///         let weight = 1. + 100. * progress.ratio.unwrap_or(1.);
+    ///         self.fitness(x) + weight * (10. - x[0] - x[1]).max(0.)
+    ///     }
+    /// }
+    /// ```
+    fn fitness_ctx(
+        &self,
+        xs: &[f64],
+        progress: &Progress<<Self::Ys as Fitness>::Eval>,
+    ) -> Self::Ys {
+        let _ = progress;
+        self.fitness(xs)
+    }
+
+    /// Return the fitness values of a batch of design variables.
+    ///
+    /// The solver calls this method with the whole batch in the pool
+    /// initialization and some methods (such as [`De`]), so the evaluations
+    /// can be dispatched at once, for example, piping the batch to an
+    /// external process.
+    ///
+    /// By default, [`ObjFunc::fitness_ctx()`] is called for each design (in
+    /// parallel if the `rayon` feature is enabled).
+    fn fitness_batch(
+        &self,
+        pool: &[Vec<f64>],
+        progress: &Progress<<Self::Ys as Fitness>::Eval>,
+    ) -> Vec<Self::Ys> {
+        #[cfg(not(feature = "rayon"))]
+        let iter = pool.iter();
+        #[cfg(feature = "rayon")]
+        let iter = pool.par_iter();
+        iter.map(|xs| self.fitness_ctx(xs, progress)).collect()
+    }
+
+    /// Estimate the gradient of the evaluation value with the step `h`.
+    ///
+    /// By default, the central finite differences of [`ObjFunc::fitness()`]
+    /// are used, which takes `2 * dim` evaluations. If a perturbed variable
+    /// is out of bound, the forward or backward difference is used instead.
+    /// Override this method if the analytic gradient is available.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{Fx, ObjFunc};
+    ///
+    /// let bound = [[0., 1.]; 2];
+    /// let f = Fx::new(&bound, |&[a, b]| a * a + b);
+    /// let g = f.grad_fd(&[0.5, 1.], 1e-6);
+    /// assert!((g[0] - 1.).abs() < 1e-6 && (g[1] - 1.).abs() < 1e-6);
+    /// ```
+    fn grad_fd(&self, xs: &[f64], h: f64) -> Vec<f64>
+    where
+        Self::Ys: Fitness<Eval = f64>,
+    {
+        let mut xs = xs.to_vec();
+        (0..xs.len())
+            .map(|s| {
+                let x = xs[s];
+                let (lo, hi) = if x + h > self.ub(s) {
+                    // Backward difference
+                    (self.clamp(s, x - h), x)
+                } else if x - h < self.lb(s) {
+                    // Forward difference
+                    (x, x + h)
+                } else {
+                    (x - h, x + h)
+                };
+                xs[s] = hi;
+                let f_hi = self.fitness(&xs).eval();
+                xs[s] = lo;
+                let f_lo = self.fitness(&xs).eval();
+                xs[s] = x;
+                (f_hi - f_lo) / (hi - lo)
+            })
+            .collect()
+    }
 }
@@ -4,10 +4,11 @@ use alloc::vec::Vec;
 use core::iter::zip;
 
 /// Single best element container.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct SingleBest<T: Fitness> {
     xs: Option<Vec<f64>>,
     ys: Option<T>,
+    eps: EpsGate<T>,
 }
 
 impl<T: Fitness> SingleBest<T> {
@@ -22,12 +23,28 @@ impl<T: Fitness> SingleBest<T> {
     }
 }
 
+/// The update statistics of the [`Pareto`] front.
+///
+/// Created by [`Pareto::stats()`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ParetoStats {
+    /// Number of the candidates that joined the front
+    pub accepted: usize,
+    /// Number of the candidates that are dominated by the front
+    pub rejected: usize,
+    /// Number of the front elements that are removed by the limit
+    pub pruned: usize,
+}
+
 /// Pareto front container for multi-objective optimization.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Pareto<T: Fitness> {
     xs: Vec<Vec<f64>>,
     ys: Vec<T>,
     limit: usize,
+    stats: Option<ParetoStats>,
+    eps: EpsGate<T>,
 }
 
 impl<T: Fitness> Pareto<T> {
@@ -51,27 +68,182 @@ impl<T: Fitness> Pareto<T> {
         self.xs.is_empty()
     }
 
+    /// Enable the update statistics, see [`Pareto::stats()`].
+    pub fn with_rank_tracking(mut self) -> Self {
+        self.set_rank_tracking(true);
+        self
+    }
+
+    /// Enable or disable the update statistics, the disabled statistics are
+    /// cleared.
+    ///
+    /// Use [`SolverBuilder::callback_mut()`] to enable it on the solver:
+    ///
+    /// ```
+    /// use metaheuristics_nature::{Rga, Solver};
+    /// # use metaheuristics_nature::tests::TestMO as MyFunc;
+    ///
+    /// let s = Solver::build(Rga::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .pareto_limit(10)
+    ///     .callback_mut(|ctx| {
+    ///         if ctx.gen == 0 {
+    ///             ctx.best.set_rank_tracking(true);
+    ///         }
+    ///     })
+    ///     .solve()
+    ///     .unwrap();
+    /// let stats = s.pareto().stats().unwrap();
+    /// println!("{stats:?}");
+    /// ```
+    pub fn set_rank_tracking(&mut self, enable: bool) {
+        match (enable, &self.stats) {
+            (true, None) => self.stats = Some(ParetoStats::default()),
+            (false, _) => self.stats = None,
+            (true, Some(_)) => (),
+        }
+    }
+
+    /// Get the update statistics since the tracking is enabled, none if it is
+    /// disabled.
+    ///
+    /// The statistics tell how the candidates are distributed, for example,
+    /// the front limit is too small if a lot of elements are pruned. The
+    /// tracking is disabled by default to avoid the overhead. See
+    /// [`Pareto::with_rank_tracking()`].
+    pub fn stats(&self) -> Option<ParetoStats> {
+        self.stats
+    }
+
     /// Get the slice of best fitness values.
     pub fn as_pareto(&self) -> &[T] {
         &self.ys
     }
 
+    /// Get the design variables of the front, in the same order as
+    /// [`Pareto::as_pareto()`].
+    pub fn front_xs(&self) -> &[Vec<f64>] {
+        &self.xs
+    }
+
+    /// Get the objective values of the front, in the same order as
+    /// [`Pareto::front_xs()`].
+    ///
+    /// ```
+    /// use metaheuristics_nature::{Rga, Solver};
+    /// # use metaheuristics_nature::tests::TestMO as MyFunc;
+    ///
+    /// let s = Solver::build(Rga::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .solve()
+    ///     .unwrap();
+    /// let front = s.as_best_set();
+    /// for (xs, ys) in front.front_xs().iter().zip(front.objectives()) {
+    ///     println!("{xs:?}, {ys:?}");
+    /// }
+    /// ```
+    pub fn objectives(&self) -> Vec<Vec<f64>>
+    where
+        T: MultiObjective,
+    {
+        self.ys.iter().map(T::objectives).collect()
+    }
+
+    /// Get the `[min, max]` range of each objective on the front.
+    ///
+    /// This is useful for the termination condition of the multi-objective
+    /// optimization, for example, stop if the front is large enough and the
+    /// ranges are stable. The result is empty if the front is empty.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{Rga, Solver};
+    /// # use metaheuristics_nature::tests::TestMO as MyFunc;
+    ///
+    /// let s = Solver::build(Rga::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 200 || ctx.best.len() >= 20)
+    ///     .solve()
+    ///     .unwrap();
+    /// for [min, max] in s.as_best_set().objective_ranges() {
+    ///     assert!(min <= max);
+    /// }
+    /// ```
+    pub fn objective_ranges(&self) -> Vec<[f64; 2]>
+    where
+        T: MultiObjective,
+    {
+        let mut iter = self.ys.iter().map(T::objectives);
+        let Some(first) = iter.next() else {
+            return Vec::new();
+        };
+        let init = first.into_iter().map(|v| [v, v]).collect::<Vec<_>>();
+        iter.fold(init, |mut ranges, obj| {
+            for ([min, max], v) in zip(&mut ranges, obj) {
+                *min = min.min(v);
+                *max = max.max(v);
+            }
+            ranges
+        })
+    }
+
+    /// Sample a random element of the front, weighted by its crowding
+    /// distance.
+    ///
+    /// The elements in the less crowded regions are more likely to be chosen,
+    /// which improves the front coverage of the methods that pull a leader
+    /// from the front. The boundary elements have the double weight of the
+    /// least crowded inner element. The distance is measured in the objective
+    /// space if [`Fitness::objective_values()`] is available, otherwise the
+    /// design variable space. See also [`crowding_distance()`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the front is empty.
+    pub fn sample_weighted(&self, rng: &mut Rng) -> (&[f64], &T) {
+        let points = crowding_points(&self.xs, &self.ys);
+        let xs = points.iter().map(Vec::as_slice).collect::<Vec<_>>();
+        let dist = crowding_distance(&xs);
+        let max = (dist.iter().copied())
+            .filter(|d| d.is_finite())
+            .fold(0., f64::max);
+        let boundary = if max > 0. { 2. * max } else { 1. };
+        let weights = (dist.into_iter())
+            .map(|d| if d.is_finite() { d } else { boundary })
+            .collect::<Vec<_>>();
+        let i = rng.weighted_index(&weights);
+        (&self.xs[i], &self.ys[i])
+    }
+
     fn update_no_limit(&mut self, xs: &[f64], ys: &T) {
         // Remove dominated solutions
         let mut has_dominated = false;
         for i in (0..self.xs.len()).rev() {
             let ys_curr = &self.ys[i];
-            if ys.is_dominated(ys_curr) {
+            if dominates(&self.eps, ys, ys_curr) {
                 has_dominated = true;
                 self.xs.swap_remove(i);
                 self.ys.swap_remove(i);
-            } else if !has_dominated && ys_curr.is_dominated(ys) {
+            } else if !has_dominated && dominates(&self.eps, ys_curr, ys) {
+                if let Some(stats) = &mut self.stats {
+                    stats.rejected += 1;
+                }
                 return;
             }
         }
         // Add the new solution
         self.xs.push(xs.to_vec());
         self.ys.push(ys.clone());
+        if let Some(stats) = &mut self.stats {
+            stats.accepted += 1;
+        }
+    }
+
+    fn record_pruned(&mut self, n: usize) {
+        if let Some(stats) = &mut self.stats {
+            stats.pruned += n;
+        }
     }
 }
 
@@ -130,18 +302,41 @@ pub trait Best: MaybeParallel {
     fn get_eval(&self) -> <Self::Item as Fitness>::Eval {
         self.as_result_fit().eval()
     }
+    /// Ignore the improvements smaller than the epsilon, the same comparison
+    /// as [`EpsDominance`]. See [`SolverBuilder::eps_dominance()`].
+    ///
+    /// The default behavior does nothing.
+    fn set_eps_dominance(&mut self, _eps: f64)
+    where
+        Self::Item: MultiObjective,
+    {
+    }
+}
+
+/// The epsilon and the objectives of the epsilon-dominance.
+type EpsGate<T> = Option<(f64, fn(&T) -> Vec<f64>)>;
+
+// Check if `a` dominates `b`, with the epsilon-dominance if it is set
+fn dominates<T: Fitness>(eps: &EpsGate<T>, a: &T, b: &T) -> bool {
+    match eps {
+        // The feasibility rules are not affected
+        Some((eps, objectives)) if a.is_feasible() == b.is_feasible() => {
+            a.is_dominated(b) && eps_better(&objectives(a), &objectives(b), *eps)
+        }
+        _ => a.is_dominated_feasible(b),
+    }
 }
 
 impl<T: Fitness> Best for SingleBest<T> {
     type Item = T;
 
     fn from_limit(_limit: usize) -> Self {
-        Self { xs: None, ys: None }
+        Self { xs: None, ys: None, eps: None }
     }
 
     fn update(&mut self, xs: &[f64], ys: &Self::Item) {
         if let (Some(best), Some(best_f)) = (&mut self.xs, &mut self.ys) {
-            if ys.is_dominated(best_f) {
+            if dominates(&self.eps, ys, best_f) {
                 *best = xs.to_vec();
                 *best_f = ys.clone();
             }
@@ -164,26 +359,36 @@ impl<T: Fitness> Best for SingleBest<T> {
     fn into_result_fit(self) -> Self::Item {
         self.ys.expect("No best element available")
     }
+
+    fn set_eps_dominance(&mut self, eps: f64)
+    where
+        T: MultiObjective,
+    {
+        self.eps = Some((eps, T::objectives));
+    }
 }
 
 impl<T: Fitness> Best for Pareto<T> {
     type Item = T;
 
     fn from_limit(limit: usize) -> Self {
-        let xs = Vec::with_capacity(limit + 1);
-        let ys = Vec::with_capacity(limit + 1);
-        Self { xs, ys, limit }
+        let cap = if limit == usize::MAX { 0 } else { limit + 1 };
+        let xs = Vec::with_capacity(cap);
+        let ys = Vec::with_capacity(cap);
+        Self { xs, ys, limit, stats: None, eps: None }
     }
 
     fn update(&mut self, xs: &[f64], ys: &Self::Item) {
         self.update_no_limit(xs, ys);
         // Prune the solution set
         if self.xs.len() > self.limit {
+            // The last one is removed if there are multiple worst elements
             let (i, _) = (self.ys.iter().map(T::eval).enumerate())
-                .max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap())
+                .max_by(|(i, a), (j, b)| cmp_eval(a, b).then(i.cmp(j)))
                 .unwrap();
             self.xs.swap_remove(i);
             self.ys.swap_remove(i);
+            self.record_pruned(1);
         }
     }
 
@@ -199,14 +404,18 @@ impl<T: Fitness> Best for Pareto<T> {
             return;
         }
         // Prune the solution set
+        // Tie-break by index, the result is deterministic in parallel
+        let cmp = |i: &usize, j: &usize| {
+            cmp_eval(&self.ys[*i].eval(), &self.ys[*j].eval()).then(i.cmp(j))
+        };
         let mut ind = (0..self.xs.len()).collect::<Vec<_>>();
         #[cfg(not(feature = "rayon"))]
-        ind.sort_unstable_by(|i, j| self.ys[*i].eval().partial_cmp(&self.ys[*j].eval()).unwrap());
+        ind.sort_unstable_by(cmp);
         #[cfg(feature = "rayon")]
-        ind.par_sort_unstable_by(|i, j| {
-            self.ys[*i].eval().partial_cmp(&self.ys[*j].eval()).unwrap()
-        });
-        // No copied vector sort
+        ind.par_sort_unstable_by(cmp);
+        // No copied vector sort, apply the permutation by following its cycles,
+        // where `ind[k]` is the source of the position `k`, and the visited
+        // positions are marked as `usize::MAX`
         for idx in 0..self.xs.len() {
             if ind[idx] != usize::MAX {
                 let mut curr_idx = idx;
@@ -222,6 +431,7 @@ impl<T: Fitness> Best for Pareto<T> {
                 }
             }
         }
+        self.record_pruned(self.xs.len() - self.limit);
         self.xs.truncate(self.limit);
         self.ys.truncate(self.limit);
     }
@@ -232,9 +442,10 @@ impl<T: Fitness> Best for Pareto<T> {
     }
 
     fn as_result(&self) -> (&[f64], &Self::Item) {
+        // The first one is chosen if there are multiple best elements
         match zip(&self.xs, &self.ys)
             .map(|(xs, ys)| (xs, ys, ys.eval()))
-            .min_by(|(.., a), (.., b)| a.partial_cmp(b).unwrap())
+            .min_by(|(.., a), (.., b)| cmp_eval(a, b))
         {
             Some((xs, ys, _)) => (xs, ys),
             None => panic!("No best element available"),
@@ -244,8 +455,53 @@ impl<T: Fitness> Best for Pareto<T> {
     fn into_result_fit(self) -> Self::Item {
         (self.ys.into_iter())
             .map(|ys| (ys.eval(), ys))
-            .min_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap())
+            .min_by(|(a, _), (b, _)| cmp_eval(a, b))
             .map(|(_, ys)| ys)
             .expect("No best element available")
     }
+
+    fn set_eps_dominance(&mut self, eps: f64)
+    where
+        T: MultiObjective,
+    {
+        self.eps = Some((eps, T::objectives));
+    }
+}
+
+/// The points to measure the crowding distance, the objective values if all
+/// of them are available, otherwise the design variables.
+pub(crate) fn crowding_points<Y: Fitness>(pool: &[Vec<f64>], pool_y: &[Y]) -> Vec<Vec<f64>> {
+    (pool_y.iter().map(Fitness::objective_values))
+        .collect::<Option<Vec<_>>>()
+        .unwrap_or_else(|| pool.to_vec())
+}
+
+/// Calculate the crowding distance of each point.
+///
+/// The distance of each variable is normalized by its range in the set, and
+/// the boundary points have an infinite distance. The larger distance means
+/// the point is in a less crowded region.
+pub fn crowding_distance(xs: &[&[f64]]) -> Vec<f64> {
+    let n = xs.len();
+    let mut dist = alloc::vec![0.; n];
+    if n <= 2 {
+        dist.fill(f64::INFINITY);
+        return dist;
+    }
+    let dim = xs[0].len();
+    let mut ind = (0..n).collect::<Vec<_>>();
+    (0..dim).for_each(|s| {
+        let v = |i: usize| xs[i][s];
+        ind.sort_unstable_by(|i, j| cmp_eval(&v(*i), &v(*j)).then(i.cmp(j)));
+        let (min, max) = (v(ind[0]), v(ind[n - 1]));
+        dist[ind[0]] = f64::INFINITY;
+        dist[ind[n - 1]] = f64::INFINITY;
+        if max - min <= 0. {
+            return;
+        }
+        for w in ind.windows(3) {
+            dist[w[1]] += (v(w[2]) - v(w[0])) / (max - min);
+        }
+    });
+    dist
 }
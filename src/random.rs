@@ -1,5 +1,7 @@
 //! Random number generator module.
 use alloc::vec::Vec;
+#[cfg(not(feature = "std"))]
+use num_traits::Float as _;
 use rand::{
     distributions::{
         uniform::{SampleRange, SampleUniform},
@@ -7,7 +9,14 @@ use rand::{
     },
     Rng as _, SeedableRng as _,
 };
-use rand_chacha::ChaCha8Rng as ChaCha;
+
+/// The backend generator of [`Rng`].
+///
+/// All the random values of this crate are generated by this type. It is the
+/// 8-round ChaCha algorithm for the speed. Change this alias to
+/// `rand_chacha::ChaCha12Rng` or `rand_chacha::ChaCha20Rng` for a stronger
+/// stream, they share the same seed, stream and word position API.
+pub type ChaCha = rand_chacha::ChaCha8Rng;
 
 /// The seed type of the ChaCha algorithm.
 pub type Seed = [u8; 32];
@@ -15,21 +24,38 @@ pub type Seed = [u8; 32];
 /// The seed option.
 ///
 /// Can be converted from `Option<u64>`, `u64`, and [`Seed`].
+///
+/// The default option is [`SeedOpt::Entropy`] if the `entropy` feature is
+/// enabled, otherwise [`SeedOpt::Counter(0)`](SeedOpt::Counter).
 #[derive(Copy, Clone)]
 pub enum SeedOpt {
     /// Seed from non-crypto u64
     U64(u64),
     /// Crypto seed series (32 bytes)
     Seed(Seed),
-    /// Auto-decided crypto seed
+    /// Auto-decided crypto seed, requires the `entropy` feature
     Entropy,
+    /// Deterministic seed from a counter (such as the run index), each value
+    /// derives an independent key, so the streams of the generators never
+    /// overlap
+    Counter(u64),
+}
+
+impl Default for SeedOpt {
+    fn default() -> Self {
+        if cfg!(feature = "entropy") {
+            Self::Entropy
+        } else {
+            Self::Counter(0)
+        }
+    }
 }
 
 impl From<Option<u64>> for SeedOpt {
     fn from(opt: Option<u64>) -> Self {
         match opt {
             Some(seed) => Self::U64(seed),
-            None => Self::Entropy,
+            None => Self::default(),
         }
     }
 }
@@ -46,6 +72,60 @@ impl From<Seed> for SeedOpt {
     }
 }
 
+/// A categorical distribution, which caches the cumulative probabilities.
+///
+/// Sampled by [`Rng::sample()`], see also [`Rng::categorical()`].
+#[derive(Clone, Debug)]
+pub struct Categorical {
+    cum: Vec<f64>,
+}
+
+impl Categorical {
+    /// Create the distribution from the probabilities, the probabilities are
+    /// not required to be normalized.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the probabilities are empty, negative, or all zero.
+    pub fn new(probs: &[f64]) -> Self {
+        assert!(
+            probs.iter().all(|p| *p >= 0.),
+            "Probabilities should be non-negative"
+        );
+        let cum = (probs.iter())
+            .scan(0., |sum, p| {
+                *sum += p;
+                Some(*sum)
+            })
+            .collect::<Vec<_>>();
+        assert!(
+            cum.last().is_some_and(|sum| *sum > 0.),
+            "Probabilities should not be empty or all zero"
+        );
+        Self { cum }
+    }
+
+    /// Get the number of the categories.
+    pub fn len(&self) -> usize {
+        self.cum.len()
+    }
+
+    /// Check if the distribution has no category.
+    pub fn is_empty(&self) -> bool {
+        self.cum.is_empty()
+    }
+}
+
+impl Distribution<usize> for Categorical {
+    fn sample<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> usize {
+        let v = rng.gen::<f64>() * self.cum[self.cum.len() - 1];
+        // Skip the zero-probability categories by the strict comparison
+        self.cum
+            .partition_point(|c| *c <= v)
+            .min(self.cum.len() - 1)
+    }
+}
+
 /// An uniformed random number generator.
 #[derive(Clone, Debug)]
 pub struct Rng {
@@ -55,21 +135,98 @@ pub struct Rng {
 impl Rng {
     /// Create generator by a given seed.
     /// If none, create the seed from CPU random function.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the seed option is [`SeedOpt::Entropy`] but the `entropy`
+    /// feature is disabled.
     pub fn new(seed: SeedOpt) -> Self {
         let rng = match seed {
             SeedOpt::Seed(seed) => ChaCha::from_seed(seed),
             SeedOpt::U64(seed) => ChaCha::seed_from_u64(seed),
+            #[cfg(feature = "entropy")]
             SeedOpt::Entropy => ChaCha::from_entropy(),
+            #[cfg(not(feature = "entropy"))]
+            SeedOpt::Entropy => panic!("Entropy seed requires the `entropy` feature"),
+            SeedOpt::Counter(n) => {
+                let mut rng = ChaCha::from_seed(Seed::default());
+                rng.set_stream(n);
+                ChaCha::from_seed(rng.gen())
+            }
         };
         Self { rng }
     }
 
+    /// Create generator from a configured backend generator.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{
+    ///     rand::SeedableRng,
+    ///     random::{ChaCha, Rng, SeedOpt},
+    /// };
+    ///
+    /// let mut rng = Rng::from_core(ChaCha::seed_from_u64(0));
+    /// assert_eq!(rng.rand(), Rng::new(SeedOpt::U64(0)).rand());
+    /// ```
+    pub fn from_core(rng: ChaCha) -> Self {
+        Self { rng }
+    }
+
     /// Seed of this generator.
     #[inline]
     pub fn seed(&self) -> Seed {
         self.rng.get_seed()
     }
 
+    /// Get the state of this generator, the seed, stream and word position.
+    ///
+    /// Use [`Rng::from_state()`] to restore the generator.
+    ///
+    /// ```
+    /// use metaheuristics_nature::random::{Rng, SeedOpt};
+    ///
+    /// let mut rng = Rng::new(SeedOpt::U64(0));
+    /// rng.rand();
+    /// let (seed, stream, pos) = rng.state();
+    /// let mut rng2 = Rng::from_state(seed, stream, pos);
+    /// assert_eq!(rng.rand(), rng2.rand());
+    /// ```
+    pub fn state(&self) -> (Seed, u64, u128) {
+        (self.seed(), self.rng.get_stream(), self.rng.get_word_pos())
+    }
+
+    /// Create generator from the state.
+    ///
+    /// See also [`Rng::state()`].
+    pub fn from_state(seed: Seed, stream: u64, word_pos: u128) -> Self {
+        let mut rng = ChaCha::from_seed(seed);
+        rng.set_stream(stream);
+        rng.set_word_pos(word_pos);
+        Self { rng }
+    }
+
+    /// Create an independent child generator without advancing this
+    /// generator.
+    ///
+    /// The child is derived from the current state deterministically, so it
+    /// can be used in the callbacks or a stochastic objective function without
+    /// affecting the random values of the algorithm. Calling this method twice
+    /// without using this generator returns the same child.
+    ///
+    /// ```
+    /// use metaheuristics_nature::random::{Rng, SeedOpt};
+    ///
+    /// let mut rng = Rng::new(SeedOpt::U64(0));
+    /// let mut rng2 = rng.clone();
+    /// let mut child = rng.split();
+    /// child.rand();
+    /// assert_eq!(rng.rand(), rng2.rand());
+    /// ```
+    pub fn split(&self) -> Self {
+        let mut rng = self.rng.clone();
+        Self { rng: ChaCha::from_seed(rng.gen()) }
+    }
+
     /// Stream for parallel threading.
     ///
     /// Use the iterators `.zip()` method to fork this RNG set.
@@ -113,6 +270,22 @@ impl Rng {
         self.rng.gen_bool(p)
     }
 
+    /// Generate a random sign, `1.` or `-1.` with the same probability.
+    #[inline]
+    pub fn sign(&mut self) -> f64 {
+        if self.rng.gen() {
+            1.
+        } else {
+            -1.
+        }
+    }
+
+    /// Generate a random boolean mask with the positive (`true`) factor for
+    /// each element independently.
+    pub fn mask(&mut self, len: usize, p: f64) -> Vec<bool> {
+        (0..len).map(|_| self.maybe(p)).collect()
+    }
+
     /// Generate a random value by range.
     #[inline]
     pub fn range<T, R>(&mut self, range: R) -> T
@@ -158,6 +331,50 @@ impl Rng {
         }
     }
 
+    /// Fill the slice with the uniform random values in the inclusive ranges
+    /// `[lower, upper]` of each element.
+    ///
+    /// ```
+    /// use metaheuristics_nature::random::{Rng, SeedOpt};
+    ///
+    /// let mut rng = Rng::new(SeedOpt::U64(0));
+    /// let bound = [[0., 1.], [-10., -5.], [3., 3.]];
+    /// let mut xs = [0.; 3];
+    /// rng.fill_range(&mut xs, &bound);
+    /// for (x, [lb, ub]) in xs.into_iter().zip(bound) {
+    ///     assert!((lb..=ub).contains(&x));
+    /// }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the lengths of the slice and the bounds are different.
+    pub fn fill_range(&mut self, out: &mut [f64], bounds: &[[f64; 2]]) {
+        assert_eq!(out.len(), bounds.len(), "Length mismatched");
+        for (x, &[lb, ub]) in out.iter_mut().zip(bounds) {
+            *x = self.range(lb..=ub);
+        }
+    }
+
+    /// Generate a random step scaled by the width of the `bound`, which is
+    /// `frac * (max - min) * range(-0.5..0.5)`.
+    ///
+    /// The methods use this function for the exploration steps, so the step
+    /// distribution is consistent across them.
+    ///
+    /// ```
+    /// use metaheuristics_nature::random::{Rng, SeedOpt};
+    ///
+    /// let mut rng = Rng::new(SeedOpt::U64(0));
+    /// let step = rng.scaled_step([-10., 10.], 0.1);
+    /// assert!((-1.0..1.).contains(&step));
+    /// ```
+    #[inline]
+    pub fn scaled_step(&mut self, bound: [f64; 2], frac: f64) -> f64 {
+        let [min, max] = bound;
+        frac * (max - min) * self.range(-0.5..0.5)
+    }
+
     /// Sample with Gaussian distribution.
     #[inline]
     pub fn normal<F>(&mut self, mean: F, std: F) -> F
@@ -168,16 +385,160 @@ impl Rng {
         self.sample(rand_distr::Normal::new(mean, std).unwrap())
     }
 
+    /// Generate a uniformly random direction in `dim` dimensions, which is a
+    /// unit vector of the normalized Gaussian samples.
+    ///
+    /// ```
+    /// use metaheuristics_nature::random::{Rng, SeedOpt};
+    ///
+    /// let mut rng = Rng::new(SeedOpt::U64(0));
+    /// let v = rng.unit_vector(4);
+    /// let norm = v.iter().map(|x| x * x).sum::<f64>().sqrt();
+    /// assert!((norm - 1.).abs() < 1e-12);
+    /// ```
+    pub fn unit_vector(&mut self, dim: usize) -> Vec<f64> {
+        if dim == 0 {
+            return Vec::new();
+        }
+        loop {
+            let mut v = (0..dim).map(|_| self.normal(0., 1.)).collect::<Vec<f64>>();
+            let norm = v.iter().map(|x| x * x).sum::<f64>().sqrt();
+            // Resample the degenerate case
+            if norm > 0. {
+                v.iter_mut().for_each(|x| *x /= norm);
+                break v;
+            }
+        }
+    }
+
+    /// Sample with the triangular distribution in `[low, high]`, where the
+    /// density is peaked at `mode`.
+    ///
+    /// The result is bounded, so it can be used as a mutation without clamping,
+    /// which piles the probability on the bounds like the Gaussian mutation.
+    ///
+    /// ```
+    /// use metaheuristics_nature::random::{Rng, SeedOpt};
+    ///
+    /// let mut rng = Rng::new(SeedOpt::U64(0));
+    /// let x = rng.triangular(-1., 3., 0.);
+    /// assert!((-1.0..=3.).contains(&x));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `low <= mode <= high` is not satisfied.
+    pub fn triangular(&mut self, low: f64, high: f64, mode: f64) -> f64 {
+        assert!(
+            low <= mode && mode <= high,
+            "Invalid triangular distribution"
+        );
+        // Inverse CDF
+        let u = self.rand();
+        let width = high - low;
+        if u * width < mode - low {
+            low + (u * width * (mode - low)).sqrt()
+        } else {
+            high - ((1. - u) * width * (high - mode)).sqrt()
+        }
+    }
+
+    /// Sample with the Beta distribution in `[0, 1]`, where `a` and `b` are
+    /// the positive shape parameters.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the shape parameters are not positive.
+    pub fn beta(&mut self, a: f64, b: f64) -> f64 {
+        self.sample(rand_distr::Beta::new(a, b).unwrap())
+    }
+
+    /// Sample a step length of the Lévy flight with Mantegna's algorithm.
+    ///
+    /// The stability index `beta` should be in `(0, 2]`, the common choice is
+    /// 1.5. The result is symmetric around zero and heavy-tailed.
+    pub fn levy(&mut self, beta: f64) -> f64 {
+        use core::f64::consts::PI;
+        let num = gamma(1. + beta) * (PI * beta * 0.5).sin();
+        let den = gamma((1. + beta) * 0.5) * beta * 2f64.powf((beta - 1.) * 0.5);
+        let sigma = (num / den).powf(beta.recip());
+        let u = self.normal(0., sigma);
+        let v = self.normal(0f64, 1.);
+        u / v.abs().powf(beta.recip())
+    }
+
     /// Shuffle a slice.
     pub fn shuffle<S: rand::seq::SliceRandom + ?Sized>(&mut self, s: &mut S) {
         s.shuffle(&mut self.rng);
     }
 
+    /// Generate a random permutation of `0..n`.
+    ///
+    /// The result is reproducible with the same seed.
+    ///
+    /// ```
+    /// use metaheuristics_nature::random::{Rng, SeedOpt};
+    ///
+    /// let mut rng = Rng::new(SeedOpt::U64(0));
+    /// let mut p = rng.permutation(10);
+    /// assert_eq!(p, Rng::new(SeedOpt::U64(0)).permutation(10));
+    /// p.sort_unstable();
+    /// assert_eq!(p, (0..10).collect::<Vec<_>>());
+    /// ```
+    pub fn permutation(&mut self, n: usize) -> Vec<usize> {
+        let mut p = (0..n).collect::<Vec<_>>();
+        self.shuffle(p.as_mut_slice());
+        p
+    }
+
     /// Choose a random value from the slice.
     pub fn choose<'a, S: rand::seq::SliceRandom + ?Sized>(&mut self, s: &'a S) -> &'a S::Item {
         s.choose(&mut self.rng).expect("Empty slice")
     }
 
+    /// Choose a random index by the weights.
+    ///
+    /// The probability of each index is proportional to its weight.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the weights are empty, negative, or all zero.
+    pub fn weighted_index(&mut self, weights: &[f64]) -> usize {
+        self.sample(rand::distributions::WeightedIndex::new(weights).unwrap())
+    }
+
+    /// Choose a category by the probabilities, the probabilities are not
+    /// required to be normalized.
+    ///
+    /// Use [`Categorical`] with [`Rng::sample()`] to cache the distribution
+    /// for the repeated sampling. For example, the adaptive formula selection
+    /// of DE, where the formulas are chosen by their recent success counts:
+    ///
+    /// ```
+    /// use metaheuristics_nature::{
+    ///     random::{Categorical, Rng, SeedOpt},
+    ///     Formula,
+    /// };
+    ///
+    /// let mut rng = Rng::new(SeedOpt::U64(0));
+    /// // Count from one to keep all formulas available
+    /// let mut success = [1.; Formula::LIST.len()];
+    /// success[0] += 4.;
+    /// let formula = Formula::LIST[rng.categorical(&success)];
+    /// // Cached for each individual
+    /// let dist = Categorical::new(&success);
+    /// let formulas = (0..10)
+    ///     .map(|_| Formula::LIST[rng.sample(&dist)])
+    ///     .collect::<Vec<_>>();
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the probabilities are empty, negative, or all zero.
+    pub fn categorical(&mut self, probs: &[f64]) -> usize {
+        self.sample(Categorical::new(probs))
+    }
+
     /// Generate a random array with no-repeat values.
     pub fn array<A, C, const N: usize>(&mut self, candi: C) -> [A; N]
     where
@@ -188,4 +549,40 @@ impl Rng {
         self.shuffle(candi.as_mut_slice());
         candi[..N].try_into().expect("candi.len() < N")
     }
+
+    /// Choose `k` no-repeat indices from `0..len`.
+    ///
+    /// This is the runtime-sized version of [`Rng::array()`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k > len`.
+    pub fn subset(&mut self, len: usize, k: usize) -> Vec<usize> {
+        rand::seq::index::sample(&mut self.rng, len, k).into_vec()
+    }
+}
+
+// Gamma function with the Lanczos approximation (g = 7, n = 9)
+fn gamma(x: f64) -> f64 {
+    use core::f64::consts::PI;
+    const G: [f64; 9] = [
+        0.999_999_999_999_809_9,
+        676.520_368_121_885_1,
+        -1_259.139_216_722_402_8,
+        771.323_428_777_653_1,
+        -176.615_029_162_140_6,
+        12.507_343_278_686_905,
+        -0.138_571_095_265_720_12,
+        9.984_369_578_019_572e-6,
+        1.505_632_735_149_311_6e-7,
+    ];
+    if x < 0.5 {
+        // Reflection formula
+        PI / ((PI * x).sin() * gamma(1. - x))
+    } else {
+        let x = x - 1.;
+        let t = x + 7.5;
+        let a = (G[1..].iter().enumerate()).fold(G[0], |a, (i, g)| a + g / (x + (i + 1) as f64));
+        (2. * PI).sqrt() * t.powf(x + 0.5) * (-t).exp() * a
+    }
 }
@@ -0,0 +1,55 @@
+#[cfg(not(feature = "std"))]
+use num_traits::Float as _;
+
+/// A parameter that changes over the generations.
+///
+/// Use [`Schedule::value()`] to get the value at a generation.
+///
+/// ```
+/// use metaheuristics_nature::Schedule;
+///
+/// let s = Schedule::Linear { from: 1., to: 0. };
+/// assert_eq!(s.value(50, Some(100)), 0.5);
+/// let s = Schedule::Exp { from: 1., rate: 0.5 };
+/// assert_eq!(s.value(2, None), 0.25);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Schedule {
+    /// Constant value.
+    Const(f64),
+    /// Linear change from `from` to `to` over the run.
+    ///
+    /// If the maximum generation number is unknown, the value reaches `to` at
+    /// the 100th generation.
+    Linear {
+        /// Initial value
+        from: f64,
+        /// Final value
+        to: f64,
+    },
+    /// Exponential change `from * rate^gen`.
+    Exp {
+        /// Initial value
+        from: f64,
+        /// Changing rate of each generation
+        rate: f64,
+    },
+}
+
+impl Schedule {
+    /// Get the value at the generation `gen`.
+    ///
+    /// The maximum generation number `max_gen` can be obtained from
+    /// [`Ctx::max_gen()`](crate::Ctx::max_gen).
+    pub fn value(&self, gen: u64, max_gen: Option<u64>) -> f64 {
+        match *self {
+            Self::Const(v) => v,
+            Self::Linear { from, to } => {
+                let r = (gen as f64 / max_gen.unwrap_or(100) as f64).min(1.);
+                from + (to - from) * r
+            }
+            Self::Exp { from, rate } => from * rate.powi(gen.min(i32::MAX as u64) as i32),
+        }
+    }
+}
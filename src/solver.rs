@@ -1,6 +1,53 @@
 use crate::prelude::*;
 use alloc::vec::Vec;
 
+/// The report of a generation.
+///
+/// Recorded by [`SolverBuilder::track_reports()`] option.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GenReport {
+    /// Generation number
+    pub gen: u64,
+    /// Best evaluation value
+    pub best_eval: f64,
+    /// Improvement from the previous generation (previous minus current)
+    pub diff: f64,
+}
+
+/// The summary of a run.
+///
+/// Created by [`Solver::summary()`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Summary {
+    /// Generation number
+    pub gens: u64,
+    /// Best evaluation value
+    pub best_eval: f64,
+    /// Mean of the evaluation values of the pool
+    pub pool_mean_eval: f64,
+    /// Standard deviation of the evaluation values of the pool
+    pub pool_std_eval: f64,
+    /// Population diversity, see [`Ctx::diversity()`]
+    pub diversity: f64,
+}
+
+/// A snapshot of the population, which can be used to seed a future run.
+///
+/// Created by [`Solver::dump_pool()`] and loaded by [`Pool::from_snapshot()`].
+/// With the `serde` feature, this type can be saved to a file.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PoolSnapshot<Y> {
+    /// Design variables of the pool
+    pub pool: Vec<Vec<f64>>,
+    /// Fitness values of the pool
+    pub pool_y: Vec<Y>,
+    /// Seed of the run
+    pub seed: Seed,
+}
+
 /// A public API for using optimization methods.
 ///
 /// Users can simply obtain their solution and see the result.
@@ -16,13 +63,20 @@ use alloc::vec::Vec;
 /// The settings are defined in the [`SolverBuilder`] type.
 #[must_use = "please call `Solver::best_parameters()` or other methods to get the answer"]
 pub struct Solver<F: ObjFunc> {
-    ctx: Ctx<F>,
-    seed: Seed,
+    pub(crate) ctx: Ctx<F>,
+    pub(crate) rng: Rng,
+    pub(crate) reports: Vec<GenReport>,
+    pub(crate) best_xs: Vec<Vec<f64>>,
 }
 
 impl<F: ObjFunc> Solver<F> {
-    pub(crate) fn new(ctx: Ctx<F>, seed: Seed) -> Self {
-        Self { ctx, seed }
+    pub(crate) fn new(
+        ctx: Ctx<F>,
+        rng: Rng,
+        reports: Vec<GenReport>,
+        best_xs: Vec<Vec<f64>>,
+    ) -> Self {
+        Self { ctx, rng, reports, best_xs }
     }
 
     /// Get the reference of the objective function.
@@ -41,6 +95,31 @@ impl<F: ObjFunc> Solver<F> {
         &self.ctx.best
     }
 
+    /// Get the reference of the Pareto front for the multi-objective
+    /// optimization.
+    ///
+    /// This is a typed version of [`Solver::as_best_set()`], which is only
+    /// available if [`Fitness::Best`] is [`Pareto`].
+    ///
+    /// ```
+    /// use metaheuristics_nature::{Rga, Solver};
+    /// # use metaheuristics_nature::tests::TestMO as MyFunc;
+    ///
+    /// let s = Solver::build(Rga::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .solve()
+    ///     .unwrap();
+    /// let front = s.pareto();
+    /// assert_eq!(front.as_pareto().len(), front.len());
+    /// ```
+    pub fn pareto(&self) -> &Pareto<F::Ys>
+    where
+        F::Ys: Fitness<Best<F::Ys> = Pareto<F::Ys>>,
+    {
+        &self.ctx.best
+    }
+
     /// Get the reference of the best parameters and the fitness value.
     pub fn as_best(&self) -> (&[f64], &F::Ys) {
         self.ctx.best.as_result()
@@ -51,6 +130,39 @@ impl<F: ObjFunc> Solver<F> {
         self.as_best().0
     }
 
+    /// Get the best parameters as an array.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{Fx, Rga, Solver};
+    ///
+    /// let bound = [[-50., 50.]; 2];
+    /// let f = Fx::new(&bound, |&[a, b]| a * a + b * b);
+    /// let s = Solver::build(Rga::default(), f)
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .solve()
+    ///     .unwrap();
+    /// let [a, b] = s.best_parameters();
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the dimension is not equal to `DIM`.
+    pub fn best_parameters<const DIM: usize>(&self) -> [f64; DIM] {
+        self.as_best_xs().try_into().expect("Dimension mismatched")
+    }
+
+    /// Get the best parameters as an owned vector.
+    pub fn best_parameters_vec(&self) -> Vec<f64> {
+        self.as_best_xs().to_vec()
+    }
+
+    /// Get the best parameters as a 1D array.
+    #[cfg(feature = "ndarray")]
+    pub fn best_xs_array(&self) -> ndarray::Array1<f64> {
+        ndarray::Array1::from_vec(self.best_parameters_vec())
+    }
+
     /// Get the reference of the best fitness value.
     pub fn as_best_fit(&self) -> &F::Ys {
         self.as_best().1
@@ -90,13 +202,295 @@ impl<F: ObjFunc> Solver<F> {
         (f, p, self.ctx.func)
     }
 
+    /// Consume and get the context of the finished run, see [`Ctx`].
+    ///
+    /// This is useful for building the analysis or the continuation tools on
+    /// top of the solver. The context is marked as non-exhaustive, so the
+    /// downstream code should treat it as read-mostly.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{Rga, Solver};
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// let ctx = Solver::build(Rga::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .solve()
+    ///     .unwrap()
+    ///     .into_ctx();
+    /// assert_eq!(ctx.gen, 20);
+    /// assert_eq!(ctx.pool.len(), ctx.pool_y.len());
+    /// ```
+    pub fn into_ctx(self) -> Ctx<F> {
+        self.ctx
+    }
+
+    /// Get the products of all individuals in the last pool.
+    ///
+    /// See also [`Solver::pool()`] for the design variables.
+    pub fn pool_products<P, Fit: Fitness>(&self) -> Vec<&P>
+    where
+        F: ObjFunc<Ys = WithProduct<Fit, P>>,
+        P: MaybeParallel + Clone + 'static,
+    {
+        self.ctx.pool_y.iter().map(WithProduct::as_result).collect()
+    }
+
+    /// Regenerate the products of all individuals in the last pool by
+    /// re-running the objective function.
+    ///
+    /// This trades the computation for the memory, the objective function
+    /// may drop the heavy products during the run, and rebuild them only when
+    /// needed. Like [`Solver::eval_at()`], the evaluations are not counted and
+    /// do not change the best solution.
+    ///
+    /// See also [`Solver::pool_products()`] for the stored products.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{Rga, Solver};
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// let s = Solver::build(Rga::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .solve()
+    ///     .unwrap();
+    /// let products = s.refit_products();
+    /// assert_eq!(products.len(), s.pool().len());
+    /// assert!(products.iter().zip(s.pool_products()).all(|(a, b)| a == b));
+    /// ```
+    pub fn refit_products<P, Fit: Fitness>(&self) -> Vec<P>
+    where
+        F: ObjFunc<Ys = WithProduct<Fit, P>>,
+        P: MaybeParallel + Clone + 'static,
+    {
+        (self.ctx.func)
+            .fitness_batch(&self.ctx.pool, &Progress::default())
+            .into_iter()
+            .map(WithProduct::into_result)
+            .collect()
+    }
+
     /// Seed of the random number generator.
     pub fn seed(&self) -> Seed {
-        self.seed
+        self.rng.seed()
     }
 
     /// Get the pool from the last status.
+    ///
+    /// See also [`Solver::pool_fit()`] for the fitness values.
     pub fn pool(&self) -> &[Vec<f64>] {
         &self.ctx.pool
     }
+
+    /// Get the fitness values of the pool from the last status, which are
+    /// aligned with [`Solver::pool()`].
+    ///
+    /// ```
+    /// use metaheuristics_nature::{Fx, Rga, Solver};
+    ///
+    /// let bound = [[-50., 50.]; 2];
+    /// let f = Fx::new(&bound, |&[a, b]| a * a + b * b);
+    /// let s = Solver::build(Rga::default(), f)
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .solve()
+    ///     .unwrap();
+    /// assert_eq!(s.pool_fit().len(), s.pool().len());
+    /// // Count the near-optimal individuals
+    /// let n = s.pool_fit().iter().filter(|&&y| y < 1e-4).count();
+    /// assert!(n > 0);
+    /// ```
+    pub fn pool_fit(&self) -> &[F::Ys] {
+        &self.ctx.pool_y
+    }
+
+    /// Evaluate the objective function at an arbitrary point.
+    ///
+    /// This is useful for the sensitivity analysis around the result. The
+    /// evaluation is not counted and does not change the best solution.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{Fx, Rga, Solver};
+    ///
+    /// let bound = [[-50., 50.]; 2];
+    /// let f = Fx::new(&bound, |&[a, b]| a * a + b * b);
+    /// let s = Solver::build(Rga::default(), f)
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .solve()
+    ///     .unwrap();
+    /// let [a, b] = s.best_parameters();
+    /// let delta = s.eval_at(&[a + 1e-3, b]) - s.get_best_eval();
+    /// ```
+    pub fn eval_at(&self, xs: &[f64]) -> F::Ys {
+        self.ctx.func.fitness(xs)
+    }
+
+    /// Take a snapshot of the pool from the last status.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{Pool, Rga, Solver};
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// let s = Solver::build(Rga::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .solve()
+    ///     .unwrap();
+    /// let snapshot = s.dump_pool();
+    /// // Resume from the last population
+    /// let s = Solver::build(Rga::default(), MyFunc::new())
+    ///     .seed(snapshot.seed)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .init_pool(Pool::from_snapshot(snapshot))
+    ///     .solve()
+    ///     .unwrap();
+    /// ```
+    pub fn dump_pool(&self) -> PoolSnapshot<F::Ys> {
+        PoolSnapshot {
+            pool: self.ctx.pool.clone(),
+            pool_y: self.ctx.pool_y.clone(),
+            seed: self.seed(),
+        }
+    }
+
+    /// Polish the best solution with the Nelder-Mead simplex method.
+    ///
+    /// The simplex is built around the current best with a step of 5% of the
+    /// bound width on each variable, and the vertices are clamped to the
+    /// bounds. The best solution is updated if a better one is found.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{Fx, Rga, Solver};
+    ///
+    /// let bound = [[-50., 50.]; 2];
+    /// let f = Fx::new(&bound, |&[a, b]| a * a + b * b);
+    /// let s = Solver::build(Rga::default(), f)
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .solve()
+    ///     .unwrap();
+    /// let err = s.get_best_eval();
+    /// let s = s.refine_nelder_mead(100);
+    /// assert!(s.get_best_eval() <= err);
+    /// ```
+    pub fn refine_nelder_mead(mut self, iters: usize) -> Self {
+        let ctx = &mut self.ctx;
+        let (xs, ys) = ctx.best.as_result();
+        let mut simplex = alloc::vec![(xs.to_vec(), ys.clone())];
+        for s in 0..ctx.dim() {
+            let mut xs = simplex[0].0.clone();
+            let step = 0.05 * ctx.bound_width(s);
+            xs[s] = if xs[s] + step <= ctx.ub(s) {
+                xs[s] + step
+            } else {
+                xs[s] - step
+            };
+            let ys = ctx.fitness(&xs);
+            simplex.push((xs, ys));
+        }
+        let lt = |a: &F::Ys, b: &F::Ys| cmp_fit(a, b).is_lt();
+        let n = simplex.len() - 1;
+        for _ in 0..iters {
+            simplex.sort_by(|(_, a), (_, b)| cmp_fit(a, b));
+            let centroid = (0..ctx.dim())
+                .map(|s| simplex[..n].iter().map(|(xs, _)| xs[s]).sum::<f64>() / n as f64)
+                .collect::<Vec<_>>();
+            // The point on the line from the centroid to the worst vertex
+            let point = |t: f64| {
+                let xs = core::iter::zip(&centroid, &simplex[n].0)
+                    .enumerate()
+                    .map(|(s, (c, w))| ctx.clamp(s, c + t * (w - c)))
+                    .collect::<Vec<_>>();
+                let ys = ctx.fitness(&xs);
+                (xs, ys)
+            };
+            let r = point(-1.);
+            let next = if lt(&r.1, &simplex[0].1) {
+                // Expansion
+                let e = point(-2.);
+                Some(if lt(&e.1, &r.1) { e } else { r })
+            } else if lt(&r.1, &simplex[n - 1].1) {
+                Some(r)
+            } else if lt(&r.1, &simplex[n].1) {
+                // Outside contraction
+                let c = point(-0.5);
+                (!lt(&r.1, &c.1)).then_some(c)
+            } else {
+                // Inside contraction
+                let c = point(0.5);
+                lt(&c.1, &simplex[n].1).then_some(c)
+            };
+            match next {
+                Some(v) => simplex[n] = v,
+                None => {
+                    // Shrink
+                    let best = simplex[0].0.clone();
+                    for (xs, ys) in &mut simplex[1..] {
+                        for (s, x) in xs.iter_mut().enumerate() {
+                            *x = ctx.clamp(s, best[s] + 0.5 * (*x - best[s]));
+                        }
+                        *ys = ctx.fitness(xs);
+                    }
+                }
+            }
+        }
+        for (xs, ys) in &simplex {
+            ctx.best.update(xs, ys);
+        }
+        self
+    }
+
+    /// Get the reports of each generation, including the initialization.
+    ///
+    /// The reports are empty unless the [`SolverBuilder::track_reports()`]
+    /// option is set.
+    pub fn reports(&self) -> &[GenReport] {
+        &self.reports
+    }
+
+    /// Get the best design variables of each generation, including the
+    /// initialization.
+    ///
+    /// The history is empty unless the [`SolverBuilder::track_best_xs()`]
+    /// option is set.
+    pub fn best_xs_history(&self) -> &[Vec<f64>] {
+        &self.best_xs
+    }
+
+    /// Get the summary of the run.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{Fx, Rga, Solver};
+    ///
+    /// let bound = [[-50., 50.]; 2];
+    /// let f = Fx::new(&bound, |&[a, b]| a * a + b * b);
+    /// let s = Solver::build(Rga::default(), f)
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .solve()
+    ///     .unwrap();
+    /// let summary = s.summary();
+    /// assert_eq!(summary.gens, 20);
+    /// assert!(summary.best_eval <= summary.pool_mean_eval);
+    /// ```
+    pub fn summary(&self) -> Summary
+    where
+        <F::Ys as Fitness>::Eval: Into<f64>,
+    {
+        let evals = (self.ctx.pool_y.iter())
+            .map(|ys| ys.eval().into())
+            .collect::<Vec<f64>>();
+        let n = evals.len() as f64;
+        let mean = evals.iter().sum::<f64>() / n;
+        let var = evals.iter().map(|y| (y - mean).powi(2)).sum::<f64>() / n;
+        Summary {
+            gens: self.ctx.gen,
+            best_eval: self.get_best_eval().into(),
+            pool_mean_eval: mean,
+            pool_std_eval: var.sqrt(),
+            diversity: self.ctx.diversity(),
+        }
+    }
 }
@@ -1,5 +1,6 @@
 use crate::prelude::*;
 use alloc::{boxed::Box, vec::Vec};
+use core::iter::zip;
 
 /// A [`SolverBuilder`] that use a boxed algorithm.
 ///
@@ -26,6 +27,41 @@ pub enum Pool<'a, F: ObjFunc> {
     /// This filter function returns true if the design variables are valid.
     #[allow(clippy::type_complexity)]
     UniformBy(maybe_send_box!(Fn(&[f64]) -> bool + 'a)),
+    /// Same as [`Pool::UniformBy`], but gives up after `max_attempts`
+    /// samples.
+    ///
+    /// The solver returns [`SolverError::InfeasibleInit`] if the pool cannot
+    /// be filled within the budget, instead of looping forever on a tiny
+    /// feasible region. The budget is recommended to be about `pop_num`
+    /// divided by the expected ratio of the feasible region, for example,
+    /// `100 * pop_num` for a 1% feasible region.
+    ///
+    /// The individuals sampled during the run (such as restarts and
+    /// immigrants) are also retried up to `max_attempts` times each, and the
+    /// last sample is used if none of them is valid.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{Pool, Rga, Solver, SolverError};
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// let pool = Pool::UniformByCapped {
+    ///     filter: Box::new(|xs| xs[0] > 49.99 && xs[1] > 49.99),
+    ///     max_attempts: 1000,
+    /// };
+    /// let e = Solver::build(Rga::default(), MyFunc::new())
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .init_pool(pool)
+    ///     .solve()
+    ///     .err();
+    /// assert!(matches!(e, Some(SolverError::InfeasibleInit { .. })));
+    /// ```
+    UniformByCapped {
+        /// Filter function, returns true if the design variables are valid
+        #[allow(clippy::type_complexity)]
+        filter: maybe_send_box!(Fn(&[f64]) -> bool + 'a),
+        /// Maximum number of samples to fill the pool
+        max_attempts: usize,
+    },
     /// Generate the pool with a specific function.
     ///
     /// The function signature is `fn(s, min..max, &rng) -> value`
@@ -44,9 +80,125 @@ pub enum Pool<'a, F: ObjFunc> {
     ///     .seed(0)
     ///     .task(|ctx| ctx.gen == 20)
     ///     .init_pool(pool)
-    ///     .solve();
+    ///     .solve()
+    ///     .unwrap();
     /// ```
     Func(PoolFunc<'a>),
+    /// Generate the pool uniformly with the opposition-based learning.
+    ///
+    /// For each sampled individual `x`, its opposite `lb + ub - x` is also
+    /// evaluated, and the better one is kept. So the objective function is
+    /// called `2 * pop_num` times in the initialization.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{Pool, Rga, Solver};
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// let s = Solver::build(Rga::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .init_pool(Pool::Opposition)
+    ///     .solve()
+    ///     .unwrap();
+    /// ```
+    Opposition,
+    /// Generate the pool with the Latin hypercube sampling.
+    ///
+    /// The bound of each variable is divided into `pop_num` strata, and each
+    /// stratum has exactly one sample at a uniformly random position. The
+    /// assignment of the strata is shuffled for each variable, so the pool
+    /// covers the search space more evenly than the uniform sampling.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{Pool, Rga, Solver};
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// let s = Solver::build(Rga::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .init_pool(Pool::LatinHypercube)
+    ///     .solve()
+    ///     .unwrap();
+    /// ```
+    LatinHypercube,
+}
+
+/// Error of the invalid solver configuration.
+///
+/// Returned by [`SolverBuilder::solve()`] before starting the algorithm.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SolverError {
+    /// The dimension size is zero.
+    ZeroDimension,
+    /// The lower bound is greater than the upper bound, or not a number.
+    InvalidBounds {
+        /// Index of the variable
+        index: usize,
+    },
+    /// The sizes of the pool and its fitness values of [`Pool::Ready`] are
+    /// not the same.
+    PoolSizeMismatch {
+        /// Size of the pool
+        expected: usize,
+        /// Size of the fitness values
+        got: usize,
+    },
+    /// The dimension of an individual of [`Pool::Ready`] or
+    /// [`SolverBuilder::seed_individual()`] is not the same as the objective
+    /// function.
+    PoolDimMismatch,
+    /// The pool of [`Pool::UniformByCapped`] cannot be filled within the
+    /// maximum number of attempts.
+    InfeasibleInit {
+        /// Number of the valid individuals found
+        found: usize,
+    },
+    /// There is no island in the [`IslandSolver`].
+    NoIsland,
+    /// The migration interval of the [`IslandSolver`] is zero.
+    ZeroMigrationInterval,
+}
+
+impl core::fmt::Display for SolverError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            Self::ZeroDimension => write!(f, "Dimension should be greater than 0"),
+            Self::InvalidBounds { index } => {
+                write!(
+                    f,
+                    "Lower bound should be less than upper bound (at {index})"
+                )
+            }
+            Self::PoolSizeMismatch { expected, got } => {
+                write!(f, "Pool size mismatched (expected {expected}, got {got})")
+            }
+            Self::PoolDimMismatch => write!(f, "Pool dimension mismatched"),
+            Self::InfeasibleInit { found } => {
+                write!(f, "Failed to fill the pool (only {found} valid)")
+            }
+            Self::NoIsland => write!(f, "No island in the model"),
+            Self::ZeroMigrationInterval => {
+                write!(f, "Migration interval should be greater than 0")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SolverError {}
+
+/// The sampling option of a variable in [`mixed_pool()`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum PoolDim {
+    /// Sample uniformly in the bound.
+    Uniform,
+    /// Sample with Gaussian distribution.
+    Gaussian {
+        /// Mean value
+        mean: f64,
+        /// Standard deviation
+        std: f64,
+    },
 }
 
 /// Collect configuration and build the solver.
@@ -66,8 +218,29 @@ pub struct SolverBuilder<'a, A: Algorithm<F>, F: ObjFunc> {
     pareto_limit: usize,
     seed: SeedOpt,
     pool: Pool<'a, F>,
+    seeds: Vec<Vec<f64>>,
     task: maybe_send_box!(FnMut(&Ctx<F>) -> bool + 'a),
-    callback: maybe_send_box!(FnMut(&Ctx<F>) + 'a),
+    max_gen: Option<u64>,
+    target: Option<<F::Ys as Fitness>::Eval>,
+    callback: maybe_send_box!(FnMut(&Ctx<F>) -> bool + 'a),
+    callback_mut: maybe_send_box!(FnMut(&mut Ctx<F>) + 'a),
+    on_improve: maybe_send_box!(FnMut(f64, f64) + 'a),
+    improve_eval: Option<fn(&Ctx<F>) -> f64>,
+    restart: Option<f64>,
+    immigrants: usize,
+    pop_min: Option<usize>,
+    report: Option<fn(&Ctx<F>) -> f64>,
+    track_best_xs: bool,
+    eps_dominance: Option<(f64, fn(&mut Ctx<F>, f64))>,
+    resample: Option<(usize, fn(&[F::Ys]) -> F::Ys)>,
+    #[cfg(feature = "std")]
+    channel: Option<std::sync::mpsc::Sender<GenReport>>,
+    #[cfg(feature = "std")]
+    surrogate: Option<maybe_send_box!(Fn(&mut Ctx<F>, f64) + 'a)>,
+    #[cfg(feature = "std")]
+    surrogate_ratio: f64,
+    #[cfg(feature = "rayon")]
+    threads: Option<usize>,
 }
 
 impl<'a, A: Algorithm<F>, F: ObjFunc> SolverBuilder<'a, A, F> {
@@ -80,6 +253,26 @@ impl<'a, A: Algorithm<F>, F: ObjFunc> SolverBuilder<'a, A, F> {
         fn pop_num(usize)
     }
 
+    /// Set the population number by the dimension of the objective function,
+    /// see [`Solver::suggest_pop_num()`].
+    ///
+    /// ```
+    /// use metaheuristics_nature::{Rga, Solver};
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// let s = Solver::build(Rga::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .auto_pop()
+    ///     .solve()
+    ///     .unwrap();
+    /// assert_eq!(s.pool().len(), 50);
+    /// ```
+    pub fn auto_pop(self) -> Self {
+        let pop_num = Solver::<F>::suggest_pop_num(self.func.dim());
+        Self { pop_num, ..self }
+    }
+
     /// Pareto front limit.
     ///
     /// It is not working for single-objective optimization.
@@ -92,7 +285,8 @@ impl<'a, A: Algorithm<F>, F: ObjFunc> SolverBuilder<'a, A, F> {
     ///     .seed(0)
     ///     .task(|ctx| ctx.gen == 20)
     ///     .pareto_limit(10)
-    ///     .solve();
+    ///     .solve()
+    ///     .unwrap();
     /// ```
     ///
     /// # Default
@@ -111,11 +305,33 @@ impl<'a, A: Algorithm<F>, F: ObjFunc> SolverBuilder<'a, A, F> {
     ///
     /// By default, the random seed is auto-decided so you cannot reproduce the
     /// result. Please print the seed via [`Solver::seed()`] method to get the
-    /// seed that used in the algorithm.
+    /// seed that used in the algorithm, or fix it before solving with
+    /// [`SolverBuilder::auto_seed()`].
     pub fn seed(self, seed: impl Into<SeedOpt>) -> Self {
         Self { seed: seed.into(), ..self }
     }
 
+    /// Fix the random seed now and return it.
+    ///
+    /// The auto-decided seed is generated eagerly, so it can be recorded
+    /// before solving. The result of [`Solver::seed()`] is the same as the
+    /// returned seed.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{Rga, Solver};
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// let mut builder = Solver::build(Rga::default(), MyFunc::new()).task(|ctx| ctx.gen == 20);
+    /// let seed = builder.auto_seed();
+    /// let s = builder.solve().unwrap();
+    /// assert_eq!(s.seed(), seed);
+    /// ```
+    pub fn auto_seed(&mut self) -> Seed {
+        let seed = Rng::new(self.seed).seed();
+        self.seed = SeedOpt::Seed(seed);
+        seed
+    }
+
     /// Initialize the pool with the pool option.
     ///
     /// # Default
@@ -126,6 +342,35 @@ impl<'a, A: Algorithm<F>, F: ObjFunc> SolverBuilder<'a, A, F> {
         Self { pool, ..self }
     }
 
+    /// Inject a known solution into the initial pool.
+    ///
+    /// After the pool is generated, the first individuals are overwritten by
+    /// the injected solutions in order, and then evaluated. The design
+    /// variables are clamped to the bounds. This method can be called
+    /// multiple times, and the solutions more than the population number are
+    /// ignored.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{Rga, Solver};
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// let s = Solver::build(Rga::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .seed_individual(vec![0.; 4])
+    ///     .solve()
+    ///     .unwrap();
+    /// assert_eq!(s.as_best_xs(), [0.; 4]);
+    /// ```
+    ///
+    /// # Default
+    ///
+    /// By default, there is no injected solution.
+    pub fn seed_individual(mut self, xs: Vec<f64>) -> Self {
+        self.seeds.push(xs);
+        self
+    }
+
     /// Termination condition.
     ///
     /// The task function will be check each iteration, breaks if the return is
@@ -138,7 +383,8 @@ impl<'a, A: Algorithm<F>, F: ObjFunc> SolverBuilder<'a, A, F> {
     /// let s = Solver::build(Rga::default(), MyFunc::new())
     ///     .seed(0)
     ///     .task(|ctx| ctx.gen == 20)
-    ///     .solve();
+    ///     .solve()
+    ///     .unwrap();
     /// ```
     ///
     /// # Default
@@ -149,7 +395,87 @@ impl<'a, A: Algorithm<F>, F: ObjFunc> SolverBuilder<'a, A, F> {
         'a: 'b,
         C: FnMut(&Ctx<F>) -> bool + Send + 'b,
     {
-        SolverBuilder { task: Box::new(task), ..self }
+        SolverBuilder { task: Box::new(task), max_gen: None, ..self }
+    }
+
+    /// Termination condition by the maximum generation number.
+    ///
+    /// This is a shortcut of [`SolverBuilder::task()`], but the maximum
+    /// generation number is also known by the algorithms, see
+    /// [`Ctx::max_gen()`] and [`Ctx::progress()`]. Some methods use it to
+    /// adjust their parameters over the run.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{Rga, Solver};
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// let s = Solver::build(Rga::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .task_gen(20)
+    ///     .solve()
+    ///     .unwrap();
+    /// ```
+    pub fn task_gen(self, max_gen: u64) -> Self {
+        Self {
+            task: Box::new(move |ctx| ctx.gen >= max_gen),
+            max_gen: Some(max_gen),
+            ..self
+        }
+    }
+
+    /// Termination condition by the number of objective function calls.
+    ///
+    /// This is a shortcut of [`SolverBuilder::task()`] with
+    /// [`Ctx::eval_count()`], which makes the comparison fair between the
+    /// algorithms that have different evaluations in each generation. The
+    /// condition is checked after each generation, so the final count may
+    /// exceed `max` slightly.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{Rga, Solver};
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// let s = Solver::build(Rga::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .pop_num(20)
+    ///     .task_evals(1000)
+    ///     .solve()
+    ///     .unwrap();
+    /// ```
+    pub fn task_evals(self, max: usize) -> Self {
+        Self {
+            task: Box::new(move |ctx| ctx.eval_count() >= max),
+            max_gen: None,
+            ..self
+        }
+    }
+
+    /// Termination condition by the target evaluation value, breaks if the
+    /// best evaluation value is lower than or equal to `target`.
+    ///
+    /// The target has the same type as the evaluation value, such as `f32` or
+    /// `f64`.
+    ///
+    /// This condition is combined with the termination condition by "or",
+    /// regardless of the order of the calls, so the target is kept even if
+    /// [`SolverBuilder::task()`] or other termination conditions are set
+    /// later.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{Fx, Rga, Solver};
+    ///
+    /// let bound = [[-50., 50.]; 2];
+    /// let f = Fx::new(&bound, |&[a, b]| a * a + b * b);
+    /// let s = Solver::build(Rga::default(), f)
+    ///     .seed(0)
+    ///     .task_gen(1000)
+    ///     .target_eval(1e-3)
+    ///     .solve()
+    ///     .unwrap();
+    /// assert!(s.get_best_eval() <= 1e-3);
+    /// ```
+    pub fn target_eval(self, target: <F::Ys as Fitness>::Eval) -> Self {
+        Self { target: Some(target), ..self }
     }
 
     /// Set callback function.
@@ -166,33 +492,532 @@ impl<'a, A: Algorithm<F>, F: ObjFunc> SolverBuilder<'a, A, F> {
     ///     .seed(0)
     ///     .task(|ctx| ctx.gen == 20)
     ///     .callback(|ctx| report.push(ctx.best.get_eval()))
-    ///     .solve();
+    ///     .solve()
+    ///     .unwrap();
+    /// ```
+    ///
+    /// The callback function can also return a boolean value (see
+    /// [`CallbackRet`]), breaks if the return is true. This condition is
+    /// combined with the [`SolverBuilder::task()`] by "or" operation.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{Rga, Solver};
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// let mut report = Vec::new();
+    /// let s = Solver::build(Rga::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .callback(|ctx| {
+    ///         report.push(ctx.best.get_eval());
+    ///         report.len() == 20
+    ///     })
+    ///     .solve()
+    ///     .unwrap();
+    /// assert_eq!(report.len(), 20);
     /// ```
     ///
     /// # Default
     ///
     /// By default, this function does nothing.
-    pub fn callback<'b, C>(self, callback: C) -> SolverBuilder<'b, A, F>
+    pub fn callback<'b, C, R>(self, mut callback: C) -> SolverBuilder<'b, A, F>
     where
         'a: 'b,
-        C: FnMut(&Ctx<F>) + Send + 'b,
+        C: FnMut(&Ctx<F>) -> R + Send + 'b,
+        R: CallbackRet,
+    {
+        let callback = Box::new(move |ctx: &Ctx<F>| callback(ctx).into_break());
+        SolverBuilder { callback, ..self }
+    }
+
+    /// Set a callback function that can modify the context.
+    ///
+    /// The function is called in each generation (including the
+    /// initialization), and then the reports, [`SolverBuilder::callback()`],
+    /// and [`SolverBuilder::task()`] are checked in order. The algorithm
+    /// generation is performed after them. So the pool can be changed between
+    /// the generations, such as injecting a known solution.
+    ///
+    /// Please call [`Ctx::find_best()`] if the pool is changed. Some methods
+    /// keep the states of each individual, so the pool size should not be
+    /// changed.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{Rga, Solver};
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// let s = Solver::build(Rga::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .callback_mut(|ctx| {
+    ///         if ctx.gen == 10 {
+    ///             // Inject a known solution
+    ///             let xs = vec![0.; 4];
+    ///             let ys = ctx.fitness(&xs);
+    ///             ctx.replace_worst(xs, ys);
+    ///             ctx.find_best();
+    ///         }
+    ///     })
+    ///     .solve()
+    ///     .unwrap();
+    /// assert_eq!(s.as_best_xs(), [0.; 4]);
+    /// ```
+    ///
+    /// # Default
+    ///
+    /// By default, this function does nothing.
+    pub fn callback_mut<'b, C>(self, callback_mut: C) -> SolverBuilder<'b, A, F>
+    where
+        'a: 'b,
+        C: FnMut(&mut Ctx<F>) + Send + 'b,
+    {
+        let callback_mut = Box::new(callback_mut);
+        SolverBuilder { callback_mut, ..self }
+    }
+
+    /// Set a callback function that is called when the best evaluation value
+    /// is improved, with the previous and the current values.
+    ///
+    /// The function is called in each generation if the best evaluation value
+    /// strictly decreases from the previous generation, so the breakthroughs
+    /// can be logged or the adaptive states can be reset without comparing
+    /// the values every step. The evaluation value should be convertible to
+    /// `f64`, such as `f32`.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{Rga, Solver};
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// let mut log = Vec::new();
+    /// let s = Solver::build(Rga::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .on_improve(|prev, curr| log.push(prev - curr))
+    ///     .solve()
+    ///     .unwrap();
+    /// assert!(log.iter().all(|diff| *diff > 0.));
+    /// ```
+    ///
+    /// # Default
+    ///
+    /// By default, this function does nothing.
+    pub fn on_improve<'b, C>(self, on_improve: C) -> SolverBuilder<'b, A, F>
+    where
+        'a: 'b,
+        C: FnMut(f64, f64) + Send + 'b,
+        <F::Ys as Fitness>::Eval: Into<f64>,
+    {
+        SolverBuilder {
+            on_improve: Box::new(on_improve),
+            improve_eval: Some(|ctx| ctx.best.get_eval().into()),
+            ..self
+        }
+    }
+
+    /// Restart the population if it is converged.
+    ///
+    /// After each generation, if the mean variance of the variables in each
+    /// dimension is lower than `var_threshold`, all individuals except the
+    /// best one are resampled by the pool option (see
+    /// [`SolverBuilder::init_pool()`]) and re-evaluated. The best container is
+    /// kept.
+    ///
+    /// This is useful for the multimodal problems, the searching is likely to
+    /// escape from the local optimum.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{De, Solver};
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// let s = Solver::build(De::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .restart_on_converge(1e-6)
+    ///     .solve()
+    ///     .unwrap();
+    /// ```
+    ///
+    /// # Default
+    ///
+    /// By default, the population is never restarted.
+    pub fn restart_on_converge(self, var_threshold: f64) -> Self {
+        Self { restart: Some(var_threshold), ..self }
+    }
+
+    /// Replace the `count` worst individuals with the random immigrants after
+    /// each generation.
+    ///
+    /// The immigrants are sampled by the initial pool option (see
+    /// [`SolverBuilder::init_pool()`]), which keeps the diversity of the
+    /// population in a long run. This is the random-immigrant technique.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{Rga, Solver};
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// let s = Solver::build(Rga::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .immigrants(5)
+    ///     .solve()
+    ///     .unwrap();
+    /// ```
+    ///
+    /// # Default
+    ///
+    /// By default, there is no immigrant.
+    pub fn immigrants(self, count: usize) -> Self {
+        Self { immigrants: count, ..self }
+    }
+
+    /// Reduce the population linearly from the initial population number to
+    /// `min` over the run, by removing the worst individuals after each
+    /// generation.
+    ///
+    /// This is the linear population size reduction of L-SHADE, which
+    /// explores with a large population first, and then exploits with a small
+    /// one. The maximum generation number is required (see
+    /// [`Ctx::progress()`]), otherwise the population is never reduced.
+    ///
+    /// The order of the remaining individuals is kept. The methods that store
+    /// the states of each individual in [`Algorithm::init()`], such as the
+    /// velocities of [`Pso`], [`Ba`], and the trial counters of [`Abc`], reset
+    /// their states when the population is resized, so the stateless methods
+    /// such as [`De`] and [`Rga`] benefit the most from it.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{De, Solver};
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// let s = Solver::build(De::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .pop_num(100)
+    ///     .task_gen(20)
+    ///     .pop_schedule(10)
+    ///     .solve()
+    ///     .unwrap();
+    /// assert_eq!(s.pool().len(), 10);
+    /// ```
+    ///
+    /// # Default
+    ///
+    /// By default, the population number is fixed.
+    pub fn pop_schedule(self, min: usize) -> Self {
+        Self { pop_min: Some(min), ..self }
+    }
+
+    /// Ignore the improvements smaller than `eps`, so the tiny floating-point
+    /// differences no longer update the best solution.
+    ///
+    /// The best container compares the fitness values like the
+    /// [`EpsDominance`] wrapper, that is, `a` dominates `b` only if at least
+    /// one of the objectives is better by more than `eps` (`a + eps < b` for a
+    /// single objective). The feasibility rules are not affected. The
+    /// convergence curve is stable, and the stall detection of the task works
+    /// as expected.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{Rga, Solver};
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// let s = Solver::build(Rga::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .eps_dominance(1e-6)
+    ///     .track_reports()
+    ///     .solve()
+    ///     .unwrap();
+    /// assert!(s.reports().iter().all(|r| r.diff == 0. || r.diff > 1e-6));
+    /// ```
+    ///
+    /// # Default
+    ///
+    /// By default, any improvement updates the best solution.
+    pub fn eps_dominance(self, eps: f64) -> Self
+    where
+        F::Ys: MultiObjective,
+    {
+        let set = |ctx: &mut Ctx<F>, eps| ctx.best.set_eps_dominance(eps);
+        Self { eps_dominance: Some((eps, set)), ..self }
+    }
+
+    /// Evaluate each design `n` times and average the fitness values, for the
+    /// noisy objective functions.
+    ///
+    /// This is the same as wrapping the objective function with [`Resample`],
+    /// but the objective function type is kept. The fitness type should
+    /// implement [`Average`], where only the fitness value of [`WithProduct`]
+    /// is averaged. The evaluation counter [`Ctx::eval_count()`] counts each
+    /// averaged design once. The resampling is disabled if `n` is less than 2.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{Fx, Rga, Solver};
+    /// use std::sync::atomic::{AtomicBool, Ordering};
+    ///
+    /// let bound = [[-50., 50.]; 2];
+    /// let flip = AtomicBool::new(false);
+    /// let f = Fx::new(&bound, |&[a, b]| {
+    ///     // A fake noise
+    ///     let noise = if flip.fetch_xor(true, Ordering::Relaxed) { -1. } else { 1. };
+    ///     a * a + b * b + 10. + noise
+    /// });
+    /// let s = Solver::build(Rga::default(), f)
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .resample(2)
+    ///     .solve()
+    ///     .unwrap();
+    /// ```
+    ///
+    /// # Default
+    ///
+    /// By default, each design is evaluated once.
+    pub fn resample(self, n: usize) -> Self
+    where
+        F::Ys: Average,
+    {
+        let resample = (n > 1).then_some((n, Average::average as fn(&[_]) -> _));
+        Self { resample, ..self }
+    }
+
+    /// Use a surrogate model to replace the expensive objective function for
+    /// the non-promising designs.
+    ///
+    /// Each design is predicted by the [`Surrogate`] model first. The design is
+    /// evaluated by the objective function if:
+    ///
+    /// + The model is not ready.
+    /// + The prediction is better than the best evaluation value of the last
+    ///   generation, that is, a promising design.
+    /// + Otherwise, a ratio of the designs (see
+    ///   [`SolverBuilder::surrogate_ratio()`]), which keeps the model
+    ///   accurate. The designs are picked by hashing their variables, so the
+    ///   result doesn't depend on the evaluation order.
+    ///
+    /// The model is trained by the true evaluations (including the initial
+    /// pool) after each generation. The best container only keeps the true
+    /// evaluations, and the predicted values are not counted by
+    /// [`Ctx::eval_count()`]. The model is dropped after the run.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{Fx, NearestSurrogate, Rga, Solver};
+    ///
+    /// let bound = [[-50., 50.]; 2];
+    /// let f = Fx::new(&bound, |&[a, b]| a * a + b * b);
+    /// let mut evals = 0;
+    /// let s = Solver::build(Rga::default(), f)
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .callback(|ctx| evals = ctx.eval_count())
+    ///     .surrogate(NearestSurrogate::new(20))
+    ///     .solve()
+    ///     .unwrap();
+    /// // Some of the designs are predicted by the model
+    /// assert!(evals < 21 * 500);
+    /// ```
+    ///
+    /// # Default
+    ///
+    /// By default, there is no surrogate model.
+    #[cfg(feature = "std")]
+    pub fn surrogate<S>(self, model: S) -> Self
+    where
+        S: Surrogate<F::Ys> + Clone + 'static,
+        <F::Ys as Fitness>::Best<F::Ys>: Clone,
+    {
+        let surrogate = Box::new(move |ctx: &mut Ctx<F>, ratio| {
+            ctx.set_surrogate(Box::new(model.clone()), ratio, Clone::clone);
+        });
+        Self { surrogate: Some(surrogate), ..self }
+    }
+
+    /// The ratio of the true evaluations for the non-promising designs, see
+    /// [`SolverBuilder::surrogate()`].
+    ///
+    /// The ratio is clamped to `0..=1`.
+    ///
+    /// # Default
+    ///
+    /// By default, the ratio is 0.1.
+    #[cfg(feature = "std")]
+    pub fn surrogate_ratio(self, ratio: f64) -> Self {
+        Self { surrogate_ratio: ratio.clamp(0., 1.), ..self }
+    }
+
+    /// Record the best evaluation value of each generation, which can be
+    /// obtained by [`Solver::reports()`].
+    ///
+    /// The evaluation value should be convertible to `f64`, such as `f32`.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{Fx, Rga, Solver};
+    ///
+    /// let bound = [[-50., 50.]; 2];
+    /// let f = Fx::new(&bound, |&[a, b]| a * a + b * b);
+    /// let s = Solver::build(Rga::default(), f)
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .track_reports()
+    ///     .solve()
+    ///     .unwrap();
+    /// assert_eq!(s.reports().len(), 21);
+    /// ```
+    ///
+    /// # Default
+    ///
+    /// By default, the reports are not recorded.
+    pub fn track_reports(self) -> Self
+    where
+        <F::Ys as Fitness>::Eval: Into<f64>,
+    {
+        Self {
+            report: Some(|ctx| ctx.best.get_eval().into()),
+            ..self
+        }
+    }
+
+    /// Record the best design variables of each generation, which can be
+    /// obtained by [`Solver::best_xs_history()`].
+    ///
+    /// The representative design of the best set is recorded, see
+    /// [`Best::as_result()`]. This option takes `dim * gen` floating-point
+    /// numbers in memory.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{Fx, Rga, Solver};
+    ///
+    /// let bound = [[-50., 50.]; 2];
+    /// let f = Fx::new(&bound, |&[a, b]| a * a + b * b);
+    /// let s = Solver::build(Rga::default(), f)
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .track_best_xs()
+    ///     .solve()
+    ///     .unwrap();
+    /// assert_eq!(s.best_xs_history().len(), 21);
+    /// assert_eq!(s.best_xs_history()[20], s.as_best_xs());
+    /// ```
+    ///
+    /// # Default
+    ///
+    /// By default, the best design variables are not recorded.
+    pub fn track_best_xs(self) -> Self {
+        Self { track_best_xs: true, ..self }
+    }
+
+    /// Send the report of each generation to the channel, including the
+    /// initialization.
+    ///
+    /// The observer can receive the reports from another thread, without
+    /// borrowing any state in the [`SolverBuilder::callback()`]. The sending
+    /// error is ignored if the receiver is dropped. This option also enables
+    /// [`SolverBuilder::track_reports()`].
+    ///
+    /// ```
+    /// use metaheuristics_nature::{Fx, Rga, Solver};
+    /// use std::sync::mpsc::channel;
+    ///
+    /// let bound = [[-50., 50.]; 2];
+    /// let f = Fx::new(&bound, |&[a, b]| a * a + b * b);
+    /// let (tx, rx) = channel();
+    /// let handle = std::thread::spawn(move || rx.iter().count());
+    /// let s = Solver::build(Rga::default(), f)
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .progress_channel(tx)
+    ///     .solve()
+    ///     .unwrap();
+    /// // The sender is dropped with the builder
+    /// assert_eq!(handle.join().unwrap(), 21);
+    /// ```
+    ///
+    /// # Default
+    ///
+    /// By default, there is no channel.
+    #[cfg(feature = "std")]
+    pub fn progress_channel(self, tx: std::sync::mpsc::Sender<GenReport>) -> Self
+    where
+        <F::Ys as Fitness>::Eval: Into<f64>,
     {
-        SolverBuilder { callback: Box::new(callback), ..self }
+        Self { channel: Some(tx), ..self.track_reports() }
+    }
+
+    /// Run the algorithm in a dedicated thread pool with `n` threads, instead
+    /// of the global thread pool.
+    ///
+    /// This is useful to limit the parallelism of each solver when running
+    /// several solvers concurrently. The result is the same as the default
+    /// thread pool with the same seed.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{Rga, Solver};
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// let s = Solver::build(Rga::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .threads(1)
+    ///     .solve()
+    ///     .unwrap();
+    /// ```
+    ///
+    /// # Default
+    ///
+    /// By default, the global thread pool of `rayon` is used.
+    #[cfg(feature = "rayon")]
+    pub fn threads(self, n: usize) -> Self {
+        Self { threads: Some(n), ..self }
+    }
+
+    /// Check the configuration without running the algorithm, and fix the
+    /// random seed like [`SolverBuilder::auto_seed()`].
+    ///
+    /// The checks are the same as [`SolverBuilder::solve()`], so the
+    /// expensive runs can fail fast and record their seeds before dispatching.
+    /// Except [`SolverError::InfeasibleInit`], which is only known after
+    /// sampling the pool.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{Rga, Solver};
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// let mut builder = Solver::build(Rga::default(), MyFunc::new()).task(|ctx| ctx.gen == 20);
+    /// let seed = builder.validate().unwrap();
+    /// let s = builder.solve().unwrap();
+    /// assert_eq!(s.seed(), seed);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// See [`SolverError`] for the details.
+    pub fn validate(&mut self) -> Result<Seed, SolverError> {
+        self.pool.check(&self.func)?;
+        check_seeds(&self.func, &self.seeds)?;
+        Ok(self.auto_seed())
     }
 
     /// Create the task and run the algorithm, which may takes a lot of time.
     ///
     /// Generation `ctx.gen` is start from 1, initialized at 0.
     ///
-    /// # Panics
+    /// # Errors
     ///
-    /// Panics before starting the algorithm if the following conditions are
-    /// met:
+    /// Returns an error before starting the algorithm if the following
+    /// conditions are met:
     /// + The dimension size is zero.
     /// + The lower bound is greater than the upper bound.
     /// + Using the [`Pool::Ready`] option and the pool size or dimension size
     ///   is not consistent.
-    pub fn solve(self) -> Solver<F> {
+    /// + The dimension size of an injected solution is not consistent.
+    /// + Using the [`Pool::UniformByCapped`] option and the pool cannot be
+    ///   filled.
+    ///
+    /// See [`SolverError`] for the details. Use
+    /// [`SolverBuilder::solve_or_panic()`] to panic on errors instead.
+    pub fn solve(self) -> Result<Solver<F>, SolverError> {
+        #[cfg(feature = "rayon")]
+        if let Some(n) = self.threads {
+            return thread_pool(n).install(|| Self { threads: None, ..self }.solve());
+        }
         let Self {
             func,
             mut algorithm,
@@ -200,60 +1025,573 @@ impl<'a, A: Algorithm<F>, F: ObjFunc> SolverBuilder<'a, A, F> {
             pareto_limit,
             seed,
             pool,
+            seeds,
             mut task,
+            max_gen,
+            target,
             mut callback,
+            mut callback_mut,
+            mut on_improve,
+            improve_eval,
+            restart,
+            immigrants,
+            pop_min,
+            report,
+            track_best_xs,
+            eps_dominance,
+            resample,
+            #[cfg(feature = "std")]
+            channel,
+            #[cfg(feature = "std")]
+            surrogate,
+            #[cfg(feature = "std")]
+            surrogate_ratio,
+            #[cfg(feature = "rayon")]
+                threads: _,
         } = self;
-        assert!(func.dim() != 0, "Dimension should be greater than 0");
-        assert!(
-            func.bound().iter().all(|[lb, ub]| lb <= ub),
-            "Lower bound should be less than upper bound"
-        );
+        pool.check(&func)?;
+        check_seeds(&func, &seeds)?;
         let mut rng = Rng::new(seed);
-        let mut ctx = match pool {
+        let (mut ctx, pool) = match pool {
             Pool::Ready { pool, pool_y } => {
-                assert_eq!(pool.len(), pool_y.len(), "Pool size mismatched");
-                let dim = func.dim();
-                pool.iter()
-                    .for_each(|xs| assert!(xs.len() == dim, "Pool dimension mismatched"));
-                Ctx::from_parts(func, pareto_limit, pool, pool_y)
+                let mut ctx = Ctx::from_parts(func, pareto_limit, pool, pool_y);
+                ctx.resample = resample;
+                (ctx, Pool::Func(uniform_pool()))
             }
-            Pool::UniformBy(filter) => {
-                let dim = func.dim();
-                let mut pool = Vec::with_capacity(pop_num);
-                let rand_f = uniform_pool();
-                while pool.len() < pop_num {
-                    let xs = (0..dim)
-                        .map(|s| rand_f(s, func.bound_range(s), &mut rng))
-                        .collect::<Vec<_>>();
+            pool => {
+                let ctx = pool.build_ctx(func, pop_num, pareto_limit, resample, &mut rng)?;
+                (ctx, pool)
+            }
+        };
+        inject_seeds(&mut ctx, &seeds);
+        ctx.max_gen = max_gen;
+        if let Some((eps, set)) = eps_dominance {
+            set(&mut ctx, eps);
+        }
+        #[cfg(feature = "std")]
+        if let Some(surrogate) = &surrogate {
+            surrogate(&mut ctx, surrogate_ratio);
+        }
+        let hooks = Hooks {
+            task: &mut task,
+            target: target.as_ref(),
+            callback: &mut callback,
+            callback_mut: &mut callback_mut,
+            on_improve: &mut on_improve,
+            improve_eval,
+            sample: &mut |func, rng| pool.sample_xs(func, rng),
+            restart,
+            immigrants,
+            pop_min,
+            report,
+            track_best_xs,
+            #[cfg(feature = "std")]
+            channel: channel.as_ref(),
+        };
+        let (reports, best_xs) = run(&mut ctx, &mut algorithm, &mut rng, hooks);
+        Ok(Solver::new(ctx, rng, reports, best_xs))
+    }
+
+    /// Same as [`SolverBuilder::solve()`], but panics on errors.
+    ///
+    /// # Panics
+    ///
+    /// Panics before starting the algorithm if the configuration is invalid.
+    /// See [`SolverError`] for the details.
+    pub fn solve_or_panic(self) -> Solver<F> {
+        self.solve().unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Run the algorithm `n` times with different seeds, and return the solver
+    /// with the best result.
+    ///
+    /// The seeds of each run are derived from the seed option. Use
+    /// [`Solver::seed()`] to get the seed of the chosen run.
+    ///
+    /// The task and callback functions are shared by all runs. If the `rayon`
+    /// feature is enabled, the runs are executed in parallel and call the
+    /// shared functions in any order, so the functions with states (such as a
+    /// stall counter) mix the states of different runs, and the result depends
+    /// on the thread scheduling. The result is determined by a fixed seed only
+    /// if the functions are stateless, or the runs are executed sequentially
+    /// with [`SolverBuilder::threads()`] set to 1.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{De, Solver};
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// let s = Solver::build(De::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .multi_start(4)
+    ///     .unwrap();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`SolverBuilder::solve()`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero.
+    pub fn multi_start(self, n: usize) -> Result<Solver<F>, SolverError>
+    where
+        A: Clone,
+        F: Clone,
+    {
+        assert!(n > 0, "The number of runs should be greater than 0");
+        #[cfg(feature = "rayon")]
+        if let Some(threads) = self.threads {
+            let s = Self { threads: None, ..self };
+            return thread_pool(threads).install(|| s.multi_start(n));
+        }
+        let Self {
+            func,
+            algorithm,
+            pop_num,
+            pareto_limit,
+            seed,
+            pool,
+            seeds,
+            mut task,
+            max_gen,
+            target,
+            mut callback,
+            mut callback_mut,
+            mut on_improve,
+            improve_eval,
+            restart,
+            immigrants,
+            pop_min,
+            report,
+            track_best_xs,
+            eps_dominance,
+            resample,
+            #[cfg(feature = "std")]
+            channel,
+            #[cfg(feature = "std")]
+            surrogate,
+            #[cfg(feature = "std")]
+            surrogate_ratio,
+            #[cfg(feature = "rayon")]
+                threads: _,
+        } = self;
+        pool.check(&func)?;
+        check_seeds(&func, &seeds)?;
+        let mut rng = Rng::new(seed);
+        let runs = (0..n)
+            .map(|_| {
+                let mut rng = Rng::new(SeedOpt::Seed(rng.gen()));
+                let mut ctx =
+                    pool.build_ctx(func.clone(), pop_num, pareto_limit, resample, &mut rng)?;
+                inject_seeds(&mut ctx, &seeds);
+                ctx.max_gen = max_gen;
+                if let Some((eps, set)) = eps_dominance {
+                    set(&mut ctx, eps);
+                }
+                #[cfg(feature = "std")]
+                if let Some(surrogate) = &surrogate {
+                    surrogate(&mut ctx, surrogate_ratio);
+                }
+                Ok((ctx, rng, algorithm.clone()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        #[cfg(not(feature = "rayon"))]
+        let iter = runs.into_iter().map(|(mut ctx, mut rng, mut algorithm)| {
+            let hooks = Hooks {
+                task: &mut task,
+                target: target.as_ref(),
+                callback: &mut callback,
+                callback_mut: &mut callback_mut,
+                on_improve: &mut on_improve,
+                improve_eval,
+                sample: &mut |func, rng| pool.sample_xs(func, rng),
+                restart,
+                immigrants,
+                pop_min,
+                report,
+                track_best_xs,
+                #[cfg(feature = "std")]
+                channel: channel.as_ref(),
+            };
+            let (reports, best_xs) = run(&mut ctx, &mut algorithm, &mut rng, hooks);
+            Solver::new(ctx, rng, reports, best_xs)
+        });
+        #[cfg(feature = "rayon")]
+        let iter = {
+            use std::sync::Mutex;
+            let task = Mutex::new(&mut task);
+            let callback = Mutex::new(&mut callback);
+            let callback_mut = Mutex::new(&mut callback_mut);
+            let on_improve = Mutex::new(&mut on_improve);
+            let pool = Mutex::new(pool);
+            runs.into_par_iter()
+                .map(|(mut ctx, mut rng, mut algorithm)| {
+                    let hooks = Hooks {
+                        task: &mut |ctx| (task.lock().unwrap())(ctx),
+                        target: target.as_ref(),
+                        callback: &mut |ctx| (callback.lock().unwrap())(ctx),
+                        callback_mut: &mut |ctx| (callback_mut.lock().unwrap())(ctx),
+                        on_improve: &mut |prev, curr| (on_improve.lock().unwrap())(prev, curr),
+                        improve_eval,
+                        sample: &mut |func, rng| pool.lock().unwrap().sample_xs(func, rng),
+                        restart,
+                        immigrants,
+                        pop_min,
+                        report,
+                        track_best_xs,
+                        #[cfg(feature = "std")]
+                        channel: channel.as_ref(),
+                    };
+                    let (reports, best_xs) = run(&mut ctx, &mut algorithm, &mut rng, hooks);
+                    Solver::new(ctx, rng, reports, best_xs)
+                })
+                .collect::<Vec<_>>()
+                .into_iter()
+        };
+        let s = iter
+            .reduce(|best, s| {
+                if cmp_eval(&s.get_best_eval(), &best.get_best_eval()).is_lt() {
+                    s
+                } else {
+                    best
+                }
+            })
+            .unwrap();
+        Ok(s)
+    }
+}
+
+impl<F: ObjFunc> Pool<'_, F> {
+    /// Create a [`Pool::Ready`] option from the snapshot, see
+    /// [`Solver::dump_pool()`].
+    ///
+    /// The seed of the snapshot is not used, set it with
+    /// [`SolverBuilder::seed()`] if needed.
+    pub fn from_snapshot(snapshot: PoolSnapshot<F::Ys>) -> Self {
+        let PoolSnapshot { pool, pool_y, .. } = snapshot;
+        Self::Ready { pool, pool_y }
+    }
+
+    /// Create a [`Pool::Ready`] option from a 2D array, where each row is an
+    /// individual.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{ndarray::Array2, ObjFunc, Pool, Rga, Solver};
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// let func = MyFunc::new();
+    /// let pool = Array2::from_elem((10, 4), 1.);
+    /// let pool_y = pool.rows().into_iter().map(|xs| func.fitness(&xs.to_vec())).collect();
+    /// let s = Solver::build(Rga::default(), func)
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .init_pool(Pool::from_array(pool, pool_y))
+    ///     .solve()
+    ///     .unwrap();
+    /// ```
+    #[cfg(feature = "ndarray")]
+    pub fn from_array(pool: ndarray::Array2<f64>, pool_y: Vec<F::Ys>) -> Self {
+        let pool = pool.rows().into_iter().map(|xs| xs.to_vec()).collect();
+        Self::Ready { pool, pool_y }
+    }
+
+    pub(crate) fn check(&self, func: &F) -> Result<(), SolverError> {
+        if func.dim() == 0 {
+            return Err(SolverError::ZeroDimension);
+        }
+        if let Some(index) = func
+            .bound()
+            .iter()
+            .position(|[lb, ub]| lb.partial_cmp(ub).is_none_or(|o| o.is_gt()))
+        {
+            return Err(SolverError::InvalidBounds { index });
+        }
+        if let Self::Ready { pool, pool_y } = self {
+            if pool.len() != pool_y.len() {
+                let (expected, got) = (pool.len(), pool_y.len());
+                return Err(SolverError::PoolSizeMismatch { expected, got });
+            }
+            if pool.iter().any(|xs| xs.len() != func.dim()) {
+                return Err(SolverError::PoolDimMismatch);
+            }
+        }
+        Ok(())
+    }
+
+    // Sample an individual, the options without a sampling function use the
+    // uniform distribution
+    fn sample_xs(&self, func: &F, rng: &mut Rng) -> Vec<f64> {
+        match self {
+            Self::UniformBy(filter) => loop {
+                let xs = uniform_xs(func, rng);
+                if filter(&xs) {
+                    break xs;
+                }
+            },
+            Self::UniformByCapped { filter, max_attempts } => {
+                let mut xs = uniform_xs(func, rng);
+                for _ in 1..*max_attempts {
                     if filter(&xs) {
-                        pool.push(xs);
+                        break;
                     }
+                    xs = uniform_xs(func, rng);
                 }
-                Ctx::from_pool(func, pareto_limit, pool)
+                xs
+            }
+            Self::Func(f) => (0..func.dim())
+                .map(|s| f(s, func.bound_range(s), rng))
+                .collect(),
+            Self::Ready { .. } | Self::Opposition | Self::LatinHypercube => uniform_xs(func, rng),
+        }
+    }
+
+    pub(crate) fn build_ctx(
+        &self,
+        func: F,
+        pop_num: usize,
+        pareto_limit: usize,
+        resample: Resampling<F::Ys>,
+        rng: &mut Rng,
+    ) -> Result<Ctx<F>, SolverError> {
+        let ctx = match self {
+            Self::Ready { pool, pool_y } => {
+                let mut ctx = Ctx::from_parts(func, pareto_limit, pool.clone(), pool_y.clone());
+                ctx.resample = resample;
+                ctx
             }
-            Pool::Func(f) => {
-                let dim = func.dim();
-                let pool = (0..pop_num)
-                    .map(|_| {
-                        (0..dim)
-                            .map(|s| f(s, func.bound_range(s), &mut rng))
+            Self::Opposition => {
+                let mut pool = (0..pop_num)
+                    .map(|_| self.sample_xs(&func, rng))
+                    .collect::<Vec<_>>();
+                let opposite = (pool.iter())
+                    .map(|xs| {
+                        zip(func.bounds_iter(), xs)
+                            .map(|([lb, ub], x)| lb + ub - x)
                             .collect()
                     })
-                    .collect();
-                Ctx::from_pool(func, pareto_limit, pool)
+                    .collect::<Vec<_>>();
+                pool.extend(opposite);
+                let mut ctx = Ctx::from_pool(func, pareto_limit, pool, resample);
+                for i in 0..pop_num {
+                    if ctx.pool_y[pop_num + i].is_dominated_feasible(&ctx.pool_y[i]) {
+                        ctx.pool.swap(i, pop_num + i);
+                        ctx.pool_y.swap(i, pop_num + i);
+                    }
+                }
+                ctx.pool.truncate(pop_num);
+                ctx.pool_y.truncate(pop_num);
+                ctx
+            }
+            Self::LatinHypercube => {
+                let mut pool = alloc::vec![alloc::vec![0.; func.dim()]; pop_num];
+                for (s, [lb, ub]) in func.bounds_iter().enumerate() {
+                    let w = (ub - lb) / pop_num as f64;
+                    for (xs, k) in zip(&mut pool, rng.permutation(pop_num)) {
+                        xs[s] = func.clamp(s, lb + (k as f64 + rng.rand()) * w);
+                    }
+                }
+                Ctx::from_pool(func, pareto_limit, pool, resample)
+            }
+            Self::UniformByCapped { filter, max_attempts } => {
+                let mut pool = Vec::with_capacity(pop_num);
+                for _ in 0..*max_attempts {
+                    if pool.len() == pop_num {
+                        break;
+                    }
+                    let xs = uniform_xs(&func, rng);
+                    if filter(&xs) {
+                        pool.push(xs);
+                    }
+                }
+                if pool.len() < pop_num {
+                    return Err(SolverError::InfeasibleInit { found: pool.len() });
+                }
+                Ctx::from_pool(func, pareto_limit, pool, resample)
+            }
+            Self::UniformBy(_) | Self::Func(_) => {
+                let pool = (0..pop_num).map(|_| self.sample_xs(&func, rng)).collect();
+                Ctx::from_pool(func, pareto_limit, pool, resample)
             }
         };
-        algorithm.init(&mut ctx, &mut rng);
-        loop {
-            callback(&ctx);
-            if task(&ctx) {
-                break;
+        Ok(ctx)
+    }
+}
+
+fn check_seeds<F: ObjFunc>(func: &F, seeds: &[Vec<f64>]) -> Result<(), SolverError> {
+    if seeds.iter().any(|xs| xs.len() != func.dim()) {
+        return Err(SolverError::PoolDimMismatch);
+    }
+    Ok(())
+}
+
+// Overwrite the first individuals with the injected solutions
+fn inject_seeds<F: ObjFunc>(ctx: &mut Ctx<F>, seeds: &[Vec<f64>]) {
+    if seeds.is_empty() {
+        return;
+    }
+    let pool = (seeds.iter().take(ctx.pop_num()))
+        .map(|xs| ctx.clamped_vec(xs))
+        .collect::<Vec<_>>();
+    let pool_y = ctx.fitness_batch(&pool);
+    for (i, (xs, ys)) in zip(pool, pool_y).enumerate() {
+        ctx.set_from(i, xs, ys);
+    }
+    ctx.find_best();
+}
+
+fn uniform_xs<F: ObjFunc>(func: &F, rng: &mut Rng) -> Vec<f64> {
+    (0..func.dim())
+        .map(|s| rng.range(func.bound_range(s)))
+        .collect()
+}
+
+#[cfg(feature = "rayon")]
+fn thread_pool(n: usize) -> rayon::ThreadPool {
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(n)
+        .build()
+        .expect("Failed to build the thread pool")
+}
+
+struct Hooks<'r, F: ObjFunc> {
+    task: &'r mut dyn FnMut(&Ctx<F>) -> bool,
+    target: Option<&'r <F::Ys as Fitness>::Eval>,
+    callback: &'r mut dyn FnMut(&Ctx<F>) -> bool,
+    callback_mut: &'r mut dyn FnMut(&mut Ctx<F>),
+    on_improve: &'r mut dyn FnMut(f64, f64),
+    improve_eval: Option<fn(&Ctx<F>) -> f64>,
+    sample: &'r mut dyn FnMut(&F, &mut Rng) -> Vec<f64>,
+    restart: Option<f64>,
+    immigrants: usize,
+    pop_min: Option<usize>,
+    report: Option<fn(&Ctx<F>) -> f64>,
+    track_best_xs: bool,
+    #[cfg(feature = "std")]
+    channel: Option<&'r std::sync::mpsc::Sender<GenReport>>,
+}
+
+type History = (Vec<GenReport>, Vec<Vec<f64>>);
+
+fn run<F, A>(ctx: &mut Ctx<F>, algorithm: &mut A, rng: &mut Rng, hooks: Hooks<F>) -> History
+where
+    F: ObjFunc,
+    A: Algorithm<F>,
+{
+    let Hooks {
+        task,
+        target,
+        callback,
+        callback_mut,
+        on_improve,
+        improve_eval,
+        sample,
+        restart,
+        immigrants,
+        pop_min,
+        report,
+        track_best_xs,
+        #[cfg(feature = "std")]
+        channel,
+    } = hooks;
+    let mut reports = Vec::new();
+    let mut best_xs = Vec::new();
+    let pop_init = ctx.pop_num();
+    let mut prev_best = None;
+    algorithm.init(ctx, rng);
+    loop {
+        #[cfg(feature = "std")]
+        ctx.train_surrogate();
+        callback_mut(ctx);
+        if let Some(eval) = improve_eval {
+            let curr = eval(ctx);
+            if let Some(prev) = prev_best.filter(|prev| curr < *prev) {
+                on_improve(prev, curr);
+            }
+            prev_best = Some(curr);
+        }
+        if let Some(report) = report {
+            let best_eval = report(ctx);
+            let diff = reports
+                .last()
+                .map_or(0., |r: &GenReport| r.best_eval - best_eval);
+            let r = GenReport { gen: ctx.gen, best_eval, diff };
+            #[cfg(feature = "std")]
+            if let Some(tx) = channel {
+                // The receiver may be dropped
+                let _ = tx.send(r);
+            }
+            reports.push(r);
+        }
+        if track_best_xs {
+            best_xs.push(ctx.best.as_result().0.to_vec());
+        }
+        if callback(ctx) || task(ctx) || target.is_some_and(|t| ctx.best.get_eval() <= *t) {
+            break;
+        }
+        ctx.gen += 1;
+        ctx.update_progress();
+        algorithm.generation(ctx, rng);
+        if restart.is_some_and(|th| pool_var(ctx) < th) {
+            // Keep the best individual in the pool
+            let best = ctx.best_indices(1)[0];
+            let others = (0..ctx.pop_num()).filter(|i| *i != best);
+            let others = others.collect::<Vec<_>>();
+            let pool = (others.iter())
+                .map(|_| sample(&ctx.func, rng))
+                .collect::<Vec<_>>();
+            let pool_y = ctx.fitness_batch(&pool);
+            for (i, (xs, ys)) in zip(others, zip(pool, pool_y)) {
+                ctx.set_from(i, xs, ys);
+            }
+            ctx.find_best();
+        }
+        if immigrants > 0 {
+            // Replace the worst individuals with the random immigrants
+            let worst = ctx.rank().into_iter().rev().take(immigrants);
+            let worst = worst.collect::<Vec<_>>();
+            let pool = (worst.iter())
+                .map(|_| sample(&ctx.func, rng))
+                .collect::<Vec<_>>();
+            let pool_y = ctx.fitness_batch(&pool);
+            for (i, (xs, ys)) in zip(worst, zip(pool, pool_y)) {
+                ctx.set_from(i, xs, ys);
+            }
+            ctx.find_best();
+        }
+        if let (Some(min), Some(ratio)) = (pop_min, ctx.progress()) {
+            // Remove the worst individuals and keep the order
+            let n = pop_init as f64 - pop_init.saturating_sub(min) as f64 * ratio;
+            let n = (n.round() as usize).max(1);
+            if n < ctx.pop_num() {
+                let mut keep = ctx.best_indices(n);
+                keep.sort_unstable();
+                ctx.pool = keep.iter().map(|&i| ctx.pool[i].clone()).collect();
+                ctx.pool_y = keep.iter().map(|&i| ctx.pool_y[i].clone()).collect();
             }
-            ctx.gen += 1;
-            algorithm.generation(&mut ctx, &mut rng);
         }
-        Solver::new(ctx, rng.seed())
     }
+    #[cfg(feature = "std")]
+    {
+        ctx.surrogate = None;
+    }
+    (reports, best_xs)
+}
+
+// Mean variance of each dimension
+fn pool_var<F: ObjFunc>(ctx: &Ctx<F>) -> f64 {
+    let n = ctx.pop_num() as f64;
+    let var = (0..ctx.dim())
+        .map(|s| {
+            let mean = ctx.pool.iter().map(|xs| xs[s]).sum::<f64>() / n;
+            ctx.pool
+                .iter()
+                .map(|xs| (xs[s] - mean).powi(2))
+                .sum::<f64>()
+                / n
+        })
+        .sum::<f64>();
+    var / ctx.dim() as f64
 }
 
 impl<F: ObjFunc> Solver<F> {
@@ -294,6 +1632,97 @@ impl<F: ObjFunc> Solver<F> {
         Self::build_default(Box::new(cfg.algorithm()), A::pop_num(), func)
     }
 
+    /// Start to build a solver with a [`Sequential`] algorithm.
+    ///
+    /// The default population number is decided by the first stage.
+    pub fn build_sequential(
+        seq: Sequential<F>,
+        func: F,
+    ) -> SolverBuilder<'static, Sequential<F>, F> {
+        let pop_num = seq.pop_num();
+        Self::build_default(seq, pop_num, func)
+    }
+
+    /// Suggest a population number for the dimension of the problem.
+    ///
+    /// This is a common heuristic `10 * dim`, clamped to `50..=1000`. Used
+    /// by [`SolverBuilder::auto_pop()`].
+    ///
+    /// ```
+    /// use metaheuristics_nature::Solver;
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// assert_eq!(Solver::<MyFunc>::suggest_pop_num(2), 50);
+    /// assert_eq!(Solver::<MyFunc>::suggest_pop_num(30), 300);
+    /// assert_eq!(Solver::<MyFunc>::suggest_pop_num(500), 1000);
+    /// ```
+    pub fn suggest_pop_num(dim: usize) -> usize {
+        dim.saturating_mul(10).clamp(50, 1000)
+    }
+
+    /// Start to build an island model solver with the algorithms of each
+    /// island, see [`IslandSolver`].
+    ///
+    /// Use [`AlgCfg::boxed()`] to mix the algorithms, and
+    /// [`IslandSolver::island()`] to append more islands.
+    pub fn build_islands<I>(islands: I, func: F) -> IslandSolver<F>
+    where
+        I: IntoIterator<Item = maybe_send_box!(Algorithm<F>)>,
+    {
+        IslandSolver::new(islands.into_iter().collect(), func)
+    }
+
+    /// Continue a finished run for `gens` more generations.
+    ///
+    /// The pool, the best set, the generation number, and the random number
+    /// generator are reused, so no re-evaluation is needed. The maximum
+    /// generation number is set to the new end, see [`Ctx::progress()`].
+    ///
+    /// The internal state of the previous algorithm (e.g. the personal bests
+    /// of PSO) is not kept, so a fresh setting is required and it will be
+    /// initialized from the current pool. The task, callback, and other
+    /// options of the builder are not kept either, and the reports are not
+    /// extended.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{Rga, Solver};
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// let s = Solver::build(Rga::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .solve()
+    ///     .unwrap();
+    /// let err = s.get_best_eval();
+    /// let s = s.continue_for(Rga::default(), 100);
+    /// assert!(s.get_best_eval() <= err);
+    /// ```
+    pub fn continue_for<A: AlgCfg>(self, cfg: A, gens: u64) -> Self {
+        let Self { mut ctx, mut rng, reports, best_xs } = self;
+        let end = ctx.gen + gens;
+        ctx.max_gen = Some(end);
+        let mut task = |ctx: &Ctx<F>| ctx.gen >= end;
+        let pool = Pool::<F>::Func(uniform_pool());
+        let hooks = Hooks {
+            task: &mut task,
+            target: None,
+            callback: &mut |_| false,
+            callback_mut: &mut |_| (),
+            on_improve: &mut |_, _| (),
+            improve_eval: None,
+            sample: &mut |func, rng| pool.sample_xs(func, rng),
+            restart: None,
+            immigrants: 0,
+            pop_min: None,
+            report: None,
+            track_best_xs: false,
+            #[cfg(feature = "std")]
+            channel: None,
+        };
+        run(&mut ctx, &mut cfg.algorithm(), &mut rng, hooks);
+        Self::new(ctx, rng, reports, best_xs)
+    }
+
     fn build_default<A: Algorithm<F>>(
         algorithm: A,
         pop_num: usize,
@@ -304,17 +1733,60 @@ impl<F: ObjFunc> Solver<F> {
             algorithm,
             pop_num,
             pareto_limit: usize::MAX,
-            seed: SeedOpt::Entropy,
+            seed: SeedOpt::default(),
             pool: Pool::Func(Box::new(uniform_pool())),
+            seeds: Vec::new(),
             task: Box::new(|ctx| ctx.gen == 200),
-            callback: Box::new(|_| ()),
+            max_gen: Some(200),
+            target: None,
+            callback: Box::new(|_| false),
+            callback_mut: Box::new(|_| ()),
+            on_improve: Box::new(|_, _| ()),
+            improve_eval: None,
+            restart: None,
+            immigrants: 0,
+            pop_min: None,
+            report: None,
+            track_best_xs: false,
+            eps_dominance: None,
+            resample: None,
+            #[cfg(feature = "std")]
+            channel: None,
+            #[cfg(feature = "std")]
+            surrogate: None,
+            #[cfg(feature = "std")]
+            surrogate_ratio: 0.1,
+            #[cfg(feature = "rayon")]
+            threads: None,
         }
     }
 }
 
+/// The return type of the callback function.
+///
+/// See [`SolverBuilder::callback()`].
+pub trait CallbackRet {
+    /// Return true to break the iteration.
+    fn into_break(self) -> bool;
+}
+
+impl CallbackRet for () {
+    #[inline]
+    fn into_break(self) -> bool {
+        false
+    }
+}
+
+impl CallbackRet for bool {
+    #[inline]
+    fn into_break(self) -> bool {
+        self
+    }
+}
+
 /// A function generates a uniform pool.
 ///
-/// See also [`gaussian_pool()`], [`Pool::Func`], and
+/// See also [`gaussian_pool()`], [`mixed_pool()`], [`Pool::Func`], and
 /// [`SolverBuilder::init_pool()`].
 pub fn uniform_pool() -> PoolFunc<'static> {
     Box::new(move |_, range, rng| rng.range(range))
@@ -324,7 +1796,7 @@ pub fn uniform_pool() -> PoolFunc<'static> {
 ///
 /// Where `mean` is the mean value, `std` is the standard deviation.
 ///
-/// See also [`uniform_pool()`], [`Pool::Func`], and
+/// See also [`uniform_pool()`], [`mixed_pool()`], [`Pool::Func`], and
 /// [`SolverBuilder::init_pool()`].
 ///
 /// # Panics
@@ -334,3 +1806,35 @@ pub fn gaussian_pool<'a>(mean: &'a [f64], std: &'a [f64]) -> PoolFunc<'a> {
     assert_eq!(mean.len(), std.len());
     Box::new(move |s, _, rng| rng.normal(mean[s], std[s]))
 }
+
+/// A function generates a pool with different sampling options of each
+/// variable.
+///
+/// This is useful when some variables have a known good region, while the
+/// others are unknown.
+///
+/// ```
+/// use metaheuristics_nature::{mixed_pool, Pool, PoolDim, Rga, Solver};
+/// # use metaheuristics_nature::tests::TestObj as MyFunc;
+///
+/// let spec = [
+///     PoolDim::Gaussian { mean: 0., std: 1. },
+///     PoolDim::Uniform,
+///     PoolDim::Uniform,
+///     PoolDim::Gaussian { mean: 1., std: 0.5 },
+/// ];
+/// let s = Solver::build(Rga::default(), MyFunc::new())
+///     .seed(0)
+///     .task(|ctx| ctx.gen == 20)
+///     .init_pool(Pool::Func(mixed_pool(&spec)))
+///     .solve()
+///     .unwrap();
+/// ```
+///
+/// See also [`uniform_pool()`], [`gaussian_pool()`], and [`Pool::Func`].
+pub fn mixed_pool(spec: &[PoolDim]) -> PoolFunc<'_> {
+    Box::new(move |s, range, rng| match spec[s] {
+        PoolDim::Uniform => rng.range(range),
+        PoolDim::Gaussian { mean, std } => rng.normal(mean, std),
+    })
+}
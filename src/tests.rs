@@ -4,6 +4,7 @@ use crate::prelude::*;
 const OFFSET: f64 = 7.;
 
 /// An example for doctest.
+#[derive(Default, Clone, Copy)]
 pub struct TestObj;
 
 impl TestObj {
@@ -29,6 +30,7 @@ impl ObjFunc for TestObj {
 }
 
 /// A multi-objective example for doctest.
+#[derive(Default, Clone, Copy)]
 pub struct TestMO;
 
 impl TestMO {
@@ -61,6 +63,16 @@ impl Fitness for TestMOFit {
     fn eval(&self) -> Self::Eval {
         self.cost.max(self.weight)
     }
+
+    fn objective_values(&self) -> Option<alloc::vec::Vec<f64>> {
+        Some(self.objectives())
+    }
+}
+
+impl MultiObjective for TestMOFit {
+    fn objectives(&self) -> alloc::vec::Vec<f64> {
+        alloc::vec![self.cost, self.weight]
+    }
 }
 
 impl ObjFunc for TestMO {
@@ -72,17 +84,45 @@ impl ObjFunc for TestMO {
     }
 }
 
+/// A multi-objective function with a continuous trade-off front.
+#[cfg(test)]
+struct TestTradeOff;
+
+#[cfg(test)]
+impl Bounded for TestTradeOff {
+    fn bound(&self) -> &[[f64; 2]] {
+        &[[-50., 50.]; 2]
+    }
+}
+
+#[cfg(test)]
+impl ObjFunc for TestTradeOff {
+    type Ys = TestMOFit;
+
+    fn fitness(&self, xs: &[f64]) -> Self::Ys {
+        let cost = xs[0] * xs[0] + xs[1] * xs[1];
+        let weight = (xs[0] - 2.).powi(2) + xs[1] * xs[1];
+        TestMOFit { cost, weight }
+    }
+}
+
 #[cfg(test)]
 fn test<S>() -> Solver<TestObj>
 where
     S: AlgCfg + Default,
 {
+    test_with(S::default())
+}
+
+#[cfg(test)]
+fn test_with<S: AlgCfg>(cfg: S) -> Solver<TestObj> {
     let mut report = alloc::vec::Vec::new();
-    let s = Solver::build(S::default(), TestObj)
+    let s = Solver::build(cfg, TestObj)
         .seed(0)
         .task(|ctx| ctx.best.as_result_fit().eval() - OFFSET < 1e-20)
         .callback(|ctx| report.push(ctx.best.get_eval()))
-        .solve();
+        .solve()
+        .unwrap();
     assert!(!report.is_empty());
     assert_eq!(s.get_best_eval(), OFFSET);
     s
@@ -97,31 +137,1123 @@ macro_rules! assert_xs {
     };
 }
 
+#[test]
+fn abc() {
+    assert_xs!(test::<Abc>());
+}
+
+#[test]
+fn abc_single_source() {
+    let s = Solver::build(Abc::default(), TestObj)
+        .seed(0)
+        .pop_num(1)
+        .task_gen(20)
+        .solve()
+        .unwrap();
+    assert_eq!(s.pool().len(), 1);
+}
+
+#[test]
+fn abc_rank_weights() {
+    // An evaluation value without the conversion to `f64`
+    #[derive(Clone, PartialEq, PartialOrd)]
+    struct Cost(f64);
+    impl EvalValue for Cost {}
+
+    let bound = [[-50., 50.]; 2];
+    let f = Fx::new(&bound, |&[a, b]| Cost(a * a + b * b));
+    let s = Solver::build(Abc::default(), f)
+        .seed(0)
+        .task_gen(200)
+        .solve()
+        .unwrap();
+    assert!(s.get_best_eval().0 < 1e-3);
+}
+
+#[test]
+fn ba() {
+    assert_xs!(test::<Ba>());
+}
+
 #[test]
 fn de() {
     assert_xs!(test::<De>());
 }
 
+#[test]
+fn de_strategies() {
+    use crate::methods::de::Crossover;
+    for (formula, crossover) in Formula::LIST
+        .into_iter()
+        .flat_map(|f| Crossover::LIST.map(|c| (f, c)))
+    {
+        let cfg = De::default().formula(formula).crossover(crossover);
+        let s = Solver::build(cfg, TestObj)
+            .seed(0)
+            .task_gen(50)
+            .solve()
+            .unwrap();
+        assert!(s.get_best_eval() - OFFSET < 1.);
+    }
+}
+
+#[test]
+#[allow(deprecated)]
+fn de_strategy_compat() {
+    for strategy in Strategy::LIST {
+        let cfg = De::default().strategy(strategy);
+        assert_eq!(Formula::LIST[strategy as usize % 6], cfg.formula);
+        assert!(
+            cfg == De::default()
+                .formula(strategy.formula())
+                .crossover(strategy.crossover())
+        );
+    }
+}
+
+#[test]
+#[cfg(feature = "serde")]
+#[allow(deprecated)]
+fn de_strategy_serde_compat() {
+    use serde::{de::value::MapDeserializer, Deserialize};
+    let de = MapDeserializer::<_, serde::de::value::Error>::new([("strategy", "C2F3")].into_iter());
+    let cfg = De::deserialize(de).unwrap();
+    assert!(cfg == De::default().strategy(Strategy::C2F3));
+}
+
+#[test]
+#[cfg(feature = "clap")]
+#[allow(deprecated)]
+fn de_strategy_clap_compat() {
+    use clap::{Parser, ValueEnum};
+    #[derive(Parser)]
+    struct Cli {
+        #[clap(flatten)]
+        de: De,
+    }
+    let strategy = Strategy::C2F3.to_possible_value().unwrap();
+    let cli = Cli::parse_from(["test", "--strategy", strategy.get_name()]);
+    assert!(cli.de == De::default().strategy(Strategy::C2F3));
+    let cli = Cli::parse_from(["test", "--formula", "f3", "--crossover", "binomial"]);
+    assert!(cli.de == De::default().strategy(Strategy::C2F3));
+}
+
+#[test]
+fn de_self_adaptive() {
+    assert_xs!(test_with(De::default().self_adaptive(true)));
+}
+
 #[test]
 fn pso() {
     assert_xs!(test::<Pso>());
 }
 
+#[test]
+fn pso_v_max() {
+    assert_xs!(test_with(Pso::default().v_max(0.2)));
+}
+
+#[test]
+fn pso_ring() {
+    assert_xs!(test_with(Pso::default().topology(Topology::Ring { k: 2 })));
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn pso_velocity_compat() {
+    use serde::{de::value::MapDeserializer, Deserialize};
+    let de = MapDeserializer::<_, serde::de::value::Error>::new([("velocity", 0.5)].into_iter());
+    let pso = Pso::deserialize(de).unwrap();
+    assert!(pso == Pso::default().velocity(0.5));
+    let de = MapDeserializer::<_, serde::de::value::Error>::new([("social", 1.)].into_iter());
+    let pso = Pso::deserialize(de).unwrap();
+    assert!(pso == Pso::default().social(1.));
+}
+
 #[test]
 fn fa() {
     assert_xs!(test::<Fa>());
 }
 
+#[test]
+fn fa_neighbors() {
+    assert_xs!(test_with(Fa::default().neighbors(10)));
+}
+
+#[test]
+fn hs() {
+    assert_xs!(test::<Hs>());
+}
+
+#[test]
+fn cmaes() {
+    assert_xs!(test::<Cmaes>());
+}
+
 #[test]
 fn rga() {
     assert_xs!(test::<Rga>());
 }
 
+#[test]
+fn rga_tournament() {
+    assert_xs!(test_with(Rga::default().tournament(5)));
+    // Out of range sizes are clamped
+    for tournament in [0, 1000] {
+        let s = Solver::build(Rga::default().tournament(tournament), TestObj)
+            .seed(0)
+            .pop_num(10)
+            .task_gen(10)
+            .solve()
+            .unwrap();
+        assert_eq!(s.pool().len(), 10);
+    }
+}
+
+#[test]
+fn rga_sbx() {
+    assert_xs!(test_with(
+        Rga::default().crossover_op(Crossover::Sbx { eta: 2. })
+    ));
+}
+
+#[test]
+fn rga_adapt_step() {
+    assert_xs!(test_with(Rga::default().adapt_step(true)));
+}
+
+#[test]
+fn rga_blx() {
+    assert_xs!(test_with(
+        Rga::default().crossover_op(Crossover::Blx { alpha: 0.5 })
+    ));
+}
+
+#[test]
+fn rga_granularity() {
+    struct Discrete;
+
+    impl Bounded for Discrete {
+        fn bound(&self) -> &[[f64; 2]] {
+            &[[-10., 10.]; 3]
+        }
+
+        fn granularity(&self, s: usize) -> Option<f64> {
+            (s < 2).then_some(0.5)
+        }
+    }
+
+    impl ObjFunc for Discrete {
+        type Ys = f64;
+        fn fitness(&self, xs: &[f64]) -> Self::Ys {
+            xs.iter().map(|x| (x - 0.3).powi(2)).sum()
+        }
+    }
+
+    let s = Solver::build(Rga::default(), Discrete)
+        .seed(0)
+        .task(|ctx| ctx.gen == 50)
+        .solve()
+        .unwrap();
+    let xs = s.as_best_xs();
+    assert_eq!(xs[..2], [0.5, 0.5]);
+    assert!((xs[2] - 0.3).abs() < 1e-3);
+}
+
 #[test]
 fn tlbo() {
     assert_xs!(test::<Tlbo>());
 }
 
+#[test]
+fn woa() {
+    assert_xs!(test::<Woa>());
+}
+
+#[test]
+fn cs() {
+    assert_xs!(test::<Cs>());
+}
+
+#[test]
+fn cs_discovery_rate_out_of_range() {
+    for pa in [-1., 2.] {
+        let s = Solver::build(Cs::default().pa(pa), TestObj)
+            .seed(0)
+            .task_gen(10)
+            .solve()
+            .unwrap();
+        assert_eq!(s.ctx.gen, 10);
+    }
+}
+
+#[test]
+fn sequential() {
+    let seq = Sequential::new(De::default(), 0.5).stage(Rga::default(), 0.5);
+    let s = Solver::build_sequential(seq, TestObj)
+        .seed(0)
+        .task(|ctx| ctx.best.as_result_fit().eval() - OFFSET < 1e-20)
+        .solve()
+        .unwrap();
+    assert_xs!(s);
+}
+
+#[test]
+fn nsga2() {
+    let s = Solver::build(Nsga2::default(), TestTradeOff)
+        .seed(0)
+        .task(|ctx| ctx.gen == 50)
+        .solve()
+        .unwrap();
+    let front = s.as_best_set().as_pareto();
+    assert!(front.len() > 10);
+    for (i, a) in front.iter().enumerate() {
+        for b in &front[i + 1..] {
+            assert!(!a.is_dominated(b) && !b.is_dominated(a));
+        }
+    }
+    // Spread across the trade-off
+    assert!(front.iter().any(|y| y.cost < 0.5));
+    assert!(front.iter().any(|y| y.weight < 0.5));
+}
+
+#[test]
+fn nsga2_single_individual() {
+    let s = Solver::build(Nsga2::default(), TestTradeOff)
+        .seed(0)
+        .pop_num(1)
+        .task_gen(20)
+        .solve()
+        .unwrap();
+    assert_eq!(s.pool().len(), 1);
+}
+
+#[test]
+fn feasible() {
+    #[derive(Clone)]
+    struct Fit(f64, bool);
+
+    impl Fitness for Fit {
+        type Best<T: Fitness> = SingleBest<T>;
+        type Eval = f64;
+        fn is_dominated(&self, rhs: &Self) -> bool {
+            self.0 < rhs.0
+        }
+        fn eval(&self) -> Self::Eval {
+            self.0
+        }
+        fn is_feasible(&self) -> bool {
+            self.1
+        }
+    }
+
+    // The unconstrained optimum is at zero, but only `x >= 1` is feasible
+    let bound = [[-50., 50.]; 2];
+    let f = Fx::new(&bound, |&[a, b]| Fit(a * a + b * b, a >= 1.));
+    let s = Solver::build(De::default(), f)
+        .seed(0)
+        .task(|ctx| ctx.gen == 100)
+        .solve()
+        .unwrap();
+    assert!(s.as_best_fit().is_feasible());
+    assert!(s.as_best_xs()[0] >= 1.);
+}
+
+#[test]
+fn nan_fitness() {
+    // The objective function is undefined when `a < 0`
+    let bound = [[-50., 50.]; 2];
+    let f = Fx::new(
+        &bound,
+        |&[a, b]| if a < 0. { f64::NAN } else { a * a + b * b },
+    );
+    let s = Solver::build(Rga::default(), f)
+        .seed(0)
+        .task(|ctx| ctx.gen == 20)
+        .solve()
+        .unwrap();
+    assert!(!s.get_best_eval().is_nan());
+    assert!(s.as_best_xs()[0] >= 0.);
+}
+
+#[test]
+fn pop_schedule() {
+    let mut sizes = alloc::vec::Vec::new();
+    let s = Solver::build(De::default(), TestObj)
+        .seed(0)
+        .pop_num(50)
+        .task_gen(10)
+        .pop_schedule(10)
+        .callback(|ctx| sizes.push(ctx.pop_num()))
+        .solve()
+        .unwrap();
+    assert_eq!(sizes[..3], [50, 46, 42]);
+    assert_eq!(s.pool().len(), 10);
+}
+
+#[test]
+fn pop_schedule_stateful() {
+    fn run<S: AlgCfg>(cfg: S) {
+        let s = Solver::build(cfg, TestObj)
+            .seed(0)
+            .pop_num(50)
+            .task_gen(10)
+            .pop_schedule(10)
+            .solve()
+            .unwrap();
+        assert_eq!(s.pool().len(), 10);
+    }
+    run(Pso::default());
+    run(Abc::default());
+    run(Ba::default());
+}
+
+#[test]
+fn latin_hypercube() {
+    let solver = Solver::build(Rga::default(), TestObj)
+        .seed(0)
+        .pop_num(10)
+        .task(|ctx| ctx.gen == 0)
+        .init_pool(Pool::LatinHypercube)
+        .solve()
+        .unwrap();
+    for s in 0..4 {
+        let mut strata = (solver.pool().iter())
+            .map(|xs| ((xs[s] + 50.) / 10.).floor() as usize)
+            .collect::<alloc::vec::Vec<_>>();
+        strata.sort_unstable();
+        assert_eq!(strata, (0..10).collect::<alloc::vec::Vec<_>>());
+    }
+}
+
+#[test]
+fn solver_error() {
+    let bound = [[-50., 50.], [1., 0.]];
+    let f = || Fx::new(&bound, |&[a, b]| a * a + b * b);
+    let e = Solver::build(De::default(), f()).validate().err();
+    assert_eq!(e, Some(SolverError::InvalidBounds { index: 1 }));
+    let e = Solver::build(De::default(), f()).solve().err();
+    assert_eq!(e, Some(SolverError::InvalidBounds { index: 1 }));
+    let pool = Pool::Ready {
+        pool: alloc::vec![alloc::vec![0.; 4]; 2],
+        pool_y: alloc::vec![WithProduct::new(OFFSET, OFFSET)],
+    };
+    let e = Solver::build(De::default(), TestObj)
+        .init_pool(pool)
+        .solve()
+        .err();
+    assert_eq!(
+        e,
+        Some(SolverError::PoolSizeMismatch { expected: 2, got: 1 })
+    );
+}
+
+#[test]
+fn uniform_by_capped() {
+    let pool = |max_attempts| Pool::UniformByCapped {
+        filter: alloc::boxed::Box::new(|xs: &[f64]| xs[0] > 0.),
+        max_attempts,
+    };
+    let e = Solver::build(De::default(), TestObj)
+        .pop_num(20)
+        .init_pool(pool(10))
+        .solve()
+        .err();
+    assert!(matches!(e, Some(SolverError::InfeasibleInit { found }) if found <= 10));
+    let s = Solver::build(De::default(), TestObj)
+        .seed(0)
+        .pop_num(20)
+        // Stop at the initialization
+        .task(|ctx| ctx.gen == 0)
+        .init_pool(pool(1000))
+        .solve()
+        .unwrap();
+    assert!(s.pool().iter().all(|xs| xs[0] > 0.));
+}
+
+#[test]
+fn seed_individual() {
+    let s = Solver::build(De::default(), TestObj)
+        .seed(0)
+        .pop_num(20)
+        .task(|ctx| ctx.gen == 0)
+        .seed_individual(alloc::vec![0.; 4])
+        .seed_individual(alloc::vec![60., 0., 0., -60.])
+        .solve()
+        .unwrap();
+    assert_eq!(s.pool()[0], [0.; 4]);
+    assert_eq!(s.pool()[1], [50., 0., 0., -50.]);
+    assert_eq!(s.as_best_xs(), [0.; 4]);
+    assert_eq!(s.pool_fit()[1].eval(), TestObj.fitness(&s.pool()[1]).eval());
+    let e = Solver::build(De::default(), TestObj)
+        .seed_individual(alloc::vec![0.; 3])
+        .solve()
+        .err();
+    assert_eq!(e, Some(SolverError::PoolDimMismatch));
+}
+
+#[test]
+fn islands() {
+    // Rastrigin function, a multimodal problem
+    #[derive(Clone)]
+    struct Rastrigin;
+    impl Bounded for Rastrigin {
+        fn bound(&self) -> &[[f64; 2]] {
+            &[[-5.12, 5.12]; 6]
+        }
+    }
+    impl ObjFunc for Rastrigin {
+        type Ys = f64;
+        fn fitness(&self, xs: &[f64]) -> Self::Ys {
+            use core::f64::consts::TAU;
+            let y = xs.iter().map(|x| x * x - 10. * (TAU * x).cos());
+            10. * xs.len() as f64 + y.sum::<f64>()
+        }
+    }
+    // Same evaluation budget
+    let single = Solver::build(De::default(), Rastrigin)
+        .seed(0)
+        .pop_num(80)
+        .task_gen(100)
+        .solve()
+        .unwrap();
+    let islands = [De::default().boxed(), De::default().boxed()];
+    let s = Solver::build_islands(islands, Rastrigin)
+        .island(Rga::default())
+        .island(Pso::default())
+        .pop_num(20)
+        .migrate_count(2)
+        .seed(0)
+        .task_gen(100)
+        .track_reports()
+        .solve()
+        .unwrap();
+    assert_eq!((s.summary().gens, s.pool().len()), (100, 20));
+    assert!(s.get_best_eval() <= single.get_best_eval());
+    assert_eq!(s.reports().len(), 101);
+    assert_eq!(s.reports()[100].best_eval, s.get_best_eval());
+    assert!(s.reports().iter().all(|r| r.diff >= 0.));
+    // Invalid settings
+    let e = Solver::build_islands([], Rastrigin).solve().err();
+    assert_eq!(e, Some(SolverError::NoIsland));
+    let e = Solver::build_islands([De::default().boxed()], Rastrigin)
+        .migrate_every(0)
+        .solve()
+        .err();
+    assert_eq!(e, Some(SolverError::ZeroMigrationInterval));
+}
+
+#[test]
+fn on_improve() {
+    let mut log = Vec::new();
+    let s = Solver::build(De::default(), TestObj)
+        .seed(0)
+        .task(|ctx| ctx.gen == 20)
+        .track_reports()
+        .on_improve(|prev, curr| log.push(prev - curr))
+        .solve()
+        .unwrap();
+    // Same as the improved generations in the reports
+    let diffs = (s.reports().iter())
+        .filter(|r| r.diff > 0.)
+        .map(|r| r.diff)
+        .collect::<Vec<_>>();
+    assert!(!log.is_empty());
+    assert_eq!(log, diffs);
+}
+
+#[test]
+fn immigrants() {
+    let run = |count| {
+        let mut evals = 0;
+        let _ = Solver::build(De::default(), TestObj)
+            .seed(0)
+            .pop_num(20)
+            .task(|ctx| ctx.gen == 10)
+            .callback(|ctx| evals = ctx.eval_count())
+            .immigrants(count)
+            .solve()
+            .unwrap();
+        evals
+    };
+    assert_eq!(run(5), run(0) + 50);
+}
+
+#[test]
+fn multi_start() {
+    let build = || {
+        Solver::build(De::default(), TestObj)
+            .pop_num(10)
+            .task(|ctx| ctx.gen == 5)
+    };
+    let s = build().seed(0).multi_start(4).unwrap();
+    let mut rng = Rng::new(SeedOpt::U64(0));
+    let best = (0..4)
+        .map(|_| {
+            let seed = rng.gen::<Seed>();
+            build().seed(seed).solve().unwrap().get_best_eval()
+        })
+        .reduce(f64::min)
+        .unwrap();
+    assert_eq!(s.get_best_eval(), best);
+    let s2 = build().seed(s.seed()).solve().unwrap();
+    assert_eq!(s2.get_best_eval(), s.get_best_eval());
+    #[cfg(feature = "rayon")]
+    {
+        let s3 = build().seed(0).threads(1).multi_start(4).unwrap();
+        assert_eq!(s3.seed(), s.seed());
+        assert_eq!(s3.get_best_eval(), s.get_best_eval());
+    }
+}
+
+#[test]
+fn restart_on_converge() {
+    let run = |threshold| {
+        let mut evals = 0;
+        let mut best = alloc::vec::Vec::new();
+        let _ = Solver::build(De::default(), TestObj)
+            .seed(0)
+            .pop_num(20)
+            .task(|ctx| ctx.gen == 10)
+            .callback(|ctx| {
+                evals = ctx.eval_count();
+                best.push(ctx.best.get_eval());
+            })
+            .restart_on_converge(threshold)
+            .solve()
+            .unwrap();
+        (evals, best)
+    };
+    let (evals, best) = run(f64::INFINITY);
+    // Restart in each generation
+    assert_eq!(evals, run(0.).0 + 10 * 19);
+    // The best is kept
+    assert!(best.windows(2).all(|w| w[1] <= w[0]));
+}
+
+#[test]
+fn replace_worst() {
+    let pool = alloc::vec![alloc::vec![0.; 4], alloc::vec![1.; 4]];
+    let mut ctx = Ctx::from_pool(TestObj, 1, pool, None);
+    assert!(!ctx.replace_worst(alloc::vec![2.; 4], TestObj.fitness(&[2.; 4])));
+    assert!(ctx.replace_worst(alloc::vec![0.5; 4], TestObj.fitness(&[0.5; 4])));
+    assert_eq!(ctx.pool[1], [0.5; 4]);
+}
+
+#[test]
+fn rank() {
+    let pool = alloc::vec![
+        alloc::vec![2.; 4],
+        alloc::vec![0.; 4],
+        alloc::vec![1.; 4],
+        alloc::vec![0.; 4],
+    ];
+    let ctx = Ctx::from_pool(TestObj, 1, pool, None);
+    assert_eq!(ctx.rank(), [1, 3, 2, 0]);
+    assert_eq!(ctx.best_indices(2), [1, 3]);
+}
+
+#[test]
+fn best_worst_index() {
+    let pool = alloc::vec![
+        alloc::vec![1.; 4],
+        alloc::vec![0.; 4],
+        alloc::vec![2.; 4],
+        alloc::vec![0.; 4],
+        alloc::vec![2.; 4],
+    ];
+    let ctx = Ctx::from_pool(TestObj, 1, pool, None);
+    assert_eq!(ctx.best_index(), 1);
+    assert_eq!(ctx.worst_index(), 4);
+}
+
+#[test]
+fn snapshot_stats() {
+    let pool = alloc::vec![alloc::vec![0.; 4], alloc::vec![1.; 4], alloc::vec![2.; 4]];
+    let ctx = Ctx::from_pool(TestObj, 1, pool, None);
+    let stats = ctx.snapshot_stats().unwrap();
+    assert_eq!(stats.min, OFFSET);
+    assert_eq!(stats.max, 44. + OFFSET);
+    assert!((stats.mean - (55. / 3. + OFFSET)).abs() < 1e-12);
+    assert_eq!(ctx.pool_view(), ctx.pool);
+    // NaN is the worst
+    let bound = [[-1., 1.]];
+    let f = Fx::new(&bound, |&[x]| if x < 0. { f64::NAN } else { x });
+    let pool = alloc::vec![alloc::vec![-1.], alloc::vec![0.], alloc::vec![1.]];
+    let stats = Ctx::from_pool(f, 1, pool, None).snapshot_stats().unwrap();
+    assert_eq!((stats.min, stats.mean), (0., 0.5));
+    assert!(stats.max.is_nan());
+    let f = Fx::new(&bound, |&[_]| f64::NAN);
+    let stats =
+        (Ctx::from_pool(f, 1, alloc::vec![alloc::vec![0.]], None).snapshot_stats()).unwrap();
+    assert!(stats.min.is_nan() && stats.mean.is_nan() && stats.max.is_nan());
+    // Empty pool
+    let ctx = Ctx::from_pool(TestObj, 1, alloc::vec::Vec::new(), None);
+    assert_eq!(ctx.snapshot_stats(), None);
+}
+
+#[test]
+fn individuals() {
+    let pool = alloc::vec![alloc::vec![0.; 4], alloc::vec![1.; 4]];
+    let mut ctx = Ctx::from_pool(TestObj, 1, pool, None);
+    let trials = (ctx.individuals())
+        .map(|(xs, _)| xs.iter().map(|x| x + 1.).collect::<alloc::vec::Vec<_>>())
+        .map(|xs| (ctx.fitness(&xs), xs))
+        .collect::<alloc::vec::Vec<_>>();
+    for ((xs, ys), (ys_new, xs_new)) in ctx.individuals_mut().zip(trials) {
+        xs.copy_from_slice(&xs_new);
+        *ys = ys_new;
+    }
+    let evals = ctx
+        .individuals()
+        .map(|(_, ys)| ys.eval())
+        .collect::<alloc::vec::Vec<_>>();
+    assert_eq!(evals, [OFFSET + 11., OFFSET + 44.]);
+    assert_eq!(ctx.pool[0], [1.; 4]);
+}
+
+#[test]
+fn gaussian_mutate() {
+    let pool = alloc::vec![alloc::vec![0.; 4], alloc::vec![50.; 4]];
+    let ctx = Ctx::from_pool(TestObj, 1, pool, None);
+    let mut rng = Rng::new(SeedOpt::U64(0));
+    for _ in 0..100 {
+        let mut xs = ctx.pool[1].clone();
+        assert!(ctx.gaussian_mutate(&mut rng, &mut xs, 1., 0.5));
+        assert!(xs.iter().all(|x| (-50.0..=50.).contains(x)));
+    }
+    let mut xs = ctx.pool[0].clone();
+    assert!(!ctx.gaussian_mutate(&mut rng, &mut xs, 0., 0.5));
+    assert_eq!(xs, [0.; 4]);
+}
+
+#[test]
+fn fitness_batch() {
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Default)]
+    struct Batch(AtomicUsize);
+
+    impl Bounded for Batch {
+        fn bound(&self) -> &[[f64; 2]] {
+            TestObj.bound()
+        }
+    }
+
+    impl ObjFunc for Batch {
+        type Ys = f64;
+        fn fitness(&self, _: &[f64]) -> Self::Ys {
+            unreachable!()
+        }
+        fn fitness_batch(&self, pool: &[Vec<f64>], _: &Progress<f64>) -> Vec<Self::Ys> {
+            self.0.fetch_add(1, Ordering::Relaxed);
+            pool.iter().map(|xs| TestObj.fitness(xs).ys()).collect()
+        }
+    }
+
+    let s = Solver::build(De::default(), Batch::default())
+        .seed(0)
+        .task(|ctx| ctx.gen == 10)
+        .solve()
+        .unwrap();
+    // Initialization and each generation
+    assert_eq!(s.func().0.load(Ordering::Relaxed), 11);
+}
+
+#[test]
+fn weighted_sum() {
+    // Only the cost is minimized, the optimum is at the origin
+    let bound = [[-50., 50.]; 2];
+    let f = Fx::new(&bound, |&[a, b]| {
+        let ys = TestMOFit {
+            cost: a * a + b * b,
+            weight: (a - 2.).powi(2) + b * b,
+        };
+        WeightedSum(ys, alloc::vec![1., 0.])
+    });
+    let s = Solver::build(De::default(), f)
+        .seed(0)
+        .task(|ctx| ctx.gen == 100)
+        .solve()
+        .unwrap();
+    assert!(s.get_best_eval() < 1e-10);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn weighted_sum_shared() {
+    use std::sync::{Arc, RwLock};
+    // Minimize the cost first, and then switch to the weight before converged
+    let weights = Arc::new(RwLock::new(alloc::vec![1., 0.]));
+    let bound = [[-50., 50.]; 2];
+    let f = Fx::new(&bound, |&[a, b]| {
+        let ys = TestMOFit {
+            cost: a * a + b * b,
+            weight: (a - 2.).powi(2) + b * b,
+        };
+        WeightedSum(ys, weights.clone())
+    });
+    let s = Solver::build(De::default(), f)
+        .seed(0)
+        .task(|ctx| ctx.gen == 200)
+        .callback(|ctx| {
+            if ctx.gen == 10 {
+                *weights.write().unwrap() = alloc::vec![0., 1.];
+            }
+        })
+        .solve()
+        .unwrap();
+    assert!(s.get_best_eval() < 1e-10);
+    let xs = s.as_best_xs();
+    assert!((xs[0] - 2.).abs() < 1e-4 && xs[1].abs() < 1e-4);
+}
+
+#[test]
+fn eps_dominance() {
+    let eps = |cost, weight| EpsDominance(TestMOFit { cost, weight }, 0.1);
+    assert!(!eps(1., 1.).is_dominated(&eps(1.05, 1.05)));
+    assert!(eps(1., 1.).is_dominated(&eps(1.05, 1.2)));
+    assert!(!eps(1., 1.3).is_dominated(&eps(1.05, 1.2)));
+    // The tiny improvements are not recorded
+    let mut best = SingleBest::from_limit(1);
+    best.update(&[0.], &EpsDominance(1., 1e-3));
+    best.update(&[1.], &EpsDominance(1. - 1e-6, 1e-3));
+    assert_eq!(best.get_xs(), [0.]);
+    // The same comparison in the best container
+    let mut best = SingleBest::from_limit(1);
+    best.set_eps_dominance(1e-3);
+    best.update(&[0.], &1.);
+    best.update(&[1.], &(1. - 1e-6));
+    assert_eq!(best.get_xs(), [0.]);
+    best.update(&[2.], &0.5);
+    assert_eq!(best.get_xs(), [2.]);
+    let s = Solver::build(Rga::default(), TestObj)
+        .seed(0)
+        .task(|ctx| ctx.gen == 20)
+        .eps_dominance(1e-3)
+        .track_reports()
+        .solve()
+        .unwrap();
+    assert!(s.reports().iter().all(|r| r.diff == 0. || r.diff > 1e-3));
+}
+
+#[test]
+fn fitness_ctx() {
+    use core::sync::atomic::{AtomicU64, Ordering};
+
+    #[derive(Default)]
+    struct Gen(AtomicU64);
+
+    impl Bounded for Gen {
+        fn bound(&self) -> &[[f64; 2]] {
+            TestObj.bound()
+        }
+    }
+
+    impl ObjFunc for Gen {
+        type Ys = f64;
+        fn fitness(&self, _: &[f64]) -> Self::Ys {
+            unreachable!()
+        }
+        fn fitness_ctx(&self, xs: &[f64], progress: &Progress<f64>) -> Self::Ys {
+            assert_eq!(progress.best_eval.is_some(), progress.gen > 0);
+            self.0.fetch_max(progress.gen, Ordering::Relaxed);
+            TestObj.fitness(xs).ys()
+        }
+    }
+
+    let s = Solver::build(Rga::default(), Gen::default())
+        .seed(0)
+        .task(|ctx| ctx.gen == 10)
+        .solve()
+        .unwrap();
+    assert_eq!(s.func().0.load(Ordering::Relaxed), 10);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn cached_obj_func() {
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    struct Counter(AtomicUsize);
+
+    impl Bounded for Counter {
+        fn bound(&self) -> &[[f64; 2]] {
+            &[[-1., 1.]; 2]
+        }
+    }
+
+    impl ObjFunc for Counter {
+        type Ys = f64;
+        fn fitness(&self, xs: &[f64]) -> Self::Ys {
+            self.0.fetch_add(1, Ordering::Relaxed);
+            xs[0] + xs[1]
+        }
+    }
+
+    let f = CachedObjFunc::new(Counter(AtomicUsize::new(0)), 1e-3);
+    assert_eq!(f.fitness(&[0.5, 0.5]), 1.);
+    assert_eq!(f.fitness(&[0.5, 0.5001]), 1.);
+    assert_eq!(f.fitness(&[0.5, 0.]), 0.5);
+    assert_eq!(f.inner().0.load(Ordering::Relaxed), 2);
+    assert_eq!(f.cache_len(), 2);
+    f.clear_cache();
+    assert_eq!(f.cache_len(), 0);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn surrogate() {
+    let run = || {
+        let mut evals = 0;
+        let s = Solver::build(Rga::default(), TestObj)
+            .seed(0)
+            .pop_num(50)
+            .task(|ctx| ctx.gen == 20)
+            .callback(|ctx| evals = ctx.eval_count())
+            .surrogate(NearestSurrogate::new(20))
+            .surrogate_ratio(0.2)
+            .solve()
+            .unwrap();
+        (s, evals)
+    };
+    let (s, evals) = run();
+    assert!(evals < 21 * 50);
+    // The best is a true evaluation
+    assert_eq!(s.func().fitness(s.as_best_xs()).eval(), s.get_best_eval());
+    // The result doesn't depend on the evaluation order
+    let (s2, evals2) = run();
+    assert_eq!(evals, evals2);
+    assert_eq!(s.as_best_xs(), s2.as_best_xs());
+}
+
+#[test]
+fn grad_fd() {
+    let bound = [[0., 1.]; 2];
+    let f = Fx::new(&bound, |&[a, b]| a * a + 2. * b);
+    let g = f.grad_fd(&[0.5, 0.5], 1e-6);
+    assert!((g[0] - 1.).abs() < 1e-6 && (g[1] - 2.).abs() < 1e-6);
+    // One-sided differences at the bounds
+    let g = f.grad_fd(&[0., 1.], 1e-6);
+    assert!(g[0].abs() < 1e-5 && (g[1] - 2.).abs() < 1e-6);
+}
+
+#[test]
+fn nelder_mead() {
+    let s = Solver::build(Rga::default(), TestObj)
+        .seed(0)
+        .task(|ctx| ctx.gen == 5)
+        .solve()
+        .unwrap();
+    let err = s.get_best_eval() - OFFSET;
+    let s = s.refine_nelder_mead(500);
+    assert!(s.get_best_eval() - OFFSET < err * 1e-6);
+}
+
+#[test]
+fn continue_for() {
+    let s = Solver::build(Pso::default(), TestObj)
+        .seed(0)
+        .task(|ctx| ctx.gen == 5)
+        .solve()
+        .unwrap();
+    let (err, seed) = (s.get_best_eval(), s.seed());
+    let s = s.continue_for(De::default(), 10);
+    assert_eq!(s.ctx.gen, 15);
+    assert_eq!(s.ctx.progress(), Some(1.));
+    assert_eq!(s.seed(), seed);
+    assert!(s.get_best_eval() <= err);
+}
+
+#[test]
+fn f32_fitness() {
+    let bound = [[-50., 50.]; 2];
+    let f = Fx::new(&bound, |&[a, b]| (a * a + b * b) as f32);
+    let s = Solver::build(De::default(), f)
+        .seed(0)
+        .task_gen(200)
+        .target_eval(1e-6f32)
+        .track_reports()
+        .solve()
+        .unwrap();
+    let eval: f32 = s.get_best_eval();
+    assert!(eval <= 1e-6);
+    assert_eq!(s.reports().last().unwrap().best_eval, eval as f64);
+}
+
+#[test]
+fn target_eval_before_task() {
+    let bound = [[-50., 50.]; 2];
+    let f = Fx::new(&bound, |&[a, b]| a * a + b * b);
+    let s = Solver::build(De::default(), f)
+        .seed(0)
+        .target_eval(1e-3)
+        .task_gen(1000)
+        .solve()
+        .unwrap();
+    assert!(s.get_best_eval() <= 1e-3);
+    assert!(s.ctx.gen < 1000);
+}
+
+#[test]
+fn pareto_tie_break() {
+    let pool = [alloc::vec![0.], alloc::vec![1.]];
+    let pool_y = [
+        TestMOFit { cost: 0., weight: 3. },
+        TestMOFit { cost: 3., weight: 0. },
+    ];
+    let mut best = Pareto::from_limit(1);
+    best.update_all(&pool, &pool_y);
+    assert_eq!(best.get_xs(), [0.]);
+}
+
+#[test]
+fn pareto_prune_pairing() {
+    let mut rng = Rng::new(SeedOpt::U64(0));
+    for _ in 0..200 {
+        let n = rng.range(2..40);
+        let limit = rng.range(1..n);
+        // The front is non-dominated, and the mirrored costs tie the evaluation
+        let pool = (0..n)
+            .map(|i| {
+                let x = if i % 3 == 0 { 0.25 } else { rng.rand() };
+                alloc::vec![if i % 5 == 0 { 1. - x } else { x }, i as f64]
+            })
+            .collect::<Vec<_>>();
+        let pool_y = (pool.iter())
+            .map(|xs| TestMOFit { cost: xs[0], weight: 1. - xs[0] })
+            .collect::<Vec<_>>();
+        let mut best = Pareto::from_limit(limit);
+        best.update_all(&pool, &pool_y);
+        assert!(best.len() <= limit);
+        for (xs, ys) in core::iter::zip(best.front_xs(), best.as_pareto()) {
+            assert_eq!((ys.cost, ys.weight), (xs[0], 1. - xs[0]));
+        }
+        // The retained elements are the best ones
+        let max = (best.as_pareto().iter())
+            .map(Fitness::eval)
+            .fold(f64::NEG_INFINITY, f64::max);
+        let kept = (best.as_pareto().iter())
+            .map(|ys| ys.cost)
+            .collect::<Vec<_>>();
+        for ys in &pool_y {
+            assert!(kept.contains(&ys.cost) || ys.eval() >= max);
+        }
+    }
+}
+
+#[test]
+fn pareto_stats() {
+    let fit = |cost, weight| TestMOFit { cost, weight };
+    // Four non-dominated candidates, and one dominated candidate
+    let pool_y = [
+        fit(0., 1.),
+        fit(1., 0.),
+        fit(0.4, 0.6),
+        fit(0.5, 0.5),
+        fit(1., 1.),
+    ];
+    let pool = alloc::vec![alloc::vec![0.]; pool_y.len()];
+    let mut best = Pareto::from_limit(2);
+    best.update_all(&pool, &pool_y);
+    assert_eq!(best.stats(), None);
+    let mut best = Pareto::from_limit(2).with_rank_tracking();
+    best.update_all(&pool, &pool_y);
+    let stats = best.stats().unwrap();
+    assert_eq!((stats.accepted, stats.rejected, stats.pruned), (4, 1, 2));
+    best.update(&pool[0], &fit(0.45, 0.45));
+    let stats = best.stats().unwrap();
+    assert_eq!((stats.accepted, stats.rejected, stats.pruned), (5, 1, 2));
+    best.set_rank_tracking(false);
+    assert_eq!(best.stats(), None);
+}
+
+#[test]
+fn sample_weighted() {
+    let pool = [0., 1., 2., 3.].map(|x| alloc::vec![x]);
+    let pool_y = [0., 0.1, 0.2, 1.].map(|cost| TestMOFit { cost, weight: 1. - cost });
+    let mut best = Pareto::from_limit(usize::MAX);
+    best.update_all(&pool, &pool_y);
+    let mut rng = Rng::new(SeedOpt::U64(0));
+    let mut count = [0; 4];
+    for _ in 0..1000 {
+        let (xs, _) = best.sample_weighted(&mut rng);
+        count[pool.iter().position(|p| p == xs).unwrap()] += 1;
+    }
+    // Weights are [3.6, 0.4, 1.8, 3.6] in the objective space
+    assert!(count[1] < count[2] && count[2] < count[0] && count[2] < count[3]);
+}
+
+#[test]
+fn objective_ranges() {
+    let pool = [alloc::vec![0.], alloc::vec![1.], alloc::vec![2.]];
+    let pool_y = [
+        TestMOFit { cost: 0., weight: 3. },
+        TestMOFit { cost: 3., weight: 0. },
+        TestMOFit { cost: 1., weight: 1. },
+    ];
+    let mut best = Pareto::from_limit(usize::MAX);
+    assert!(best.objective_ranges().is_empty());
+    best.update_all(&pool, &pool_y);
+    assert_eq!(best.objective_ranges(), [[0., 3.], [0., 3.]]);
+}
+
+#[test]
+fn permutation() {
+    let mut rng = Rng::new(SeedOpt::U64(0));
+    let mut count = [[0usize; 4]; 4];
+    for _ in 0..24000 {
+        for (i, v) in rng.permutation(4).into_iter().enumerate() {
+            count[i][v] += 1;
+        }
+    }
+    // Each value appears at each position with the same probability
+    for c in count.into_iter().flatten() {
+        assert!((5700..6300).contains(&c), "count: {c}");
+    }
+}
+
+#[test]
+fn categorical() {
+    let mut rng = Rng::new(SeedOpt::U64(0));
+    let dist = random::Categorical::new(&[0., 3., 0., 1.]);
+    let mut count = [0; 4];
+    for _ in 0..1000 {
+        count[rng.sample(&dist)] += 1;
+    }
+    assert_eq!((count[0], count[2]), (0, 0));
+    assert!(count[1] > 2 * count[3]);
+    assert_eq!(rng.categorical(&[0., 0., 1.]), 2);
+}
+
+#[test]
+fn triangular_beta() {
+    let mut rng = Rng::new(SeedOpt::U64(0));
+    let n = 10000;
+    let mean = (0..n).map(|_| rng.triangular(0., 3., 0.)).sum::<f64>() / n as f64;
+    assert!((mean - 1.).abs() < 0.05);
+    let mean = (0..n).map(|_| rng.beta(2., 6.)).sum::<f64>() / n as f64;
+    assert!((mean - 0.25).abs() < 0.01);
+    assert_eq!(rng.triangular(1., 1., 1.), 1.);
+}
+
+#[test]
+fn seed_counter() {
+    let mut rng1 = Rng::new(SeedOpt::Counter(1));
+    let mut rng2 = Rng::new(SeedOpt::Counter(1));
+    let mut rng3 = Rng::new(SeedOpt::Counter(2));
+    let a = rng1.rand();
+    assert_eq!(a, rng2.rand());
+    assert_ne!(a, rng3.rand());
+    // The key is derived from the counter
+    assert_eq!(rng1.seed(), rng2.seed());
+    assert_ne!(rng1.seed(), rng3.seed());
+    assert_ne!(rng1.seed(), Seed::default());
+    // The child streams don't overlap with the other counters
+    let child = rng1.stream(2).pop().unwrap();
+    assert_ne!(child.seed(), rng3.seed());
+}
+
 #[cfg(feature = "rayon")]
 #[test]
 fn test_rng() {
@@ -142,3 +1274,90 @@ fn test_rng() {
         assert_eq!(non_parallel, parallel);
     }
 }
+
+#[cfg(feature = "rayon")]
+#[test]
+fn threads() {
+    let run = |threads| {
+        let s = Solver::build(De::default(), TestObj)
+            .seed(0)
+            .task(|ctx| ctx.gen == 20);
+        let s = match threads {
+            Some(n) => s.threads(n),
+            None => s,
+        };
+        s.solve().unwrap().best_parameters_vec()
+    };
+    assert_eq!(run(Some(1)), run(None));
+    assert_eq!(run(Some(2)), run(None));
+}
+
+#[test]
+#[cfg(feature = "ndarray")]
+fn ndarray_pool() {
+    let pool = ndarray::Array2::from_shape_fn((10, 4), |(i, s)| (i + s) as f64);
+    let pool_y = (pool.rows().into_iter())
+        .map(|xs| TestObj.fitness(&xs.to_vec()))
+        .collect();
+    let s = Solver::build(De::default(), TestObj)
+        .seed(0)
+        .task(|ctx| ctx.gen == 0)
+        .init_pool(Pool::from_array(pool, pool_y))
+        .solve()
+        .unwrap();
+    assert_eq!(s.pool()[3], [3., 4., 5., 6.]);
+    assert_eq!(s.best_xs_array().to_vec(), s.as_best_xs());
+}
+
+#[test]
+fn resample() {
+    use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    let flip = AtomicBool::new(false);
+    let calls = AtomicUsize::new(0);
+    let bound = [[-50., 50.]; 2];
+    // The noise of each pair of calls is cancelled in total
+    let f = Fx::new(&bound, |&[a, b]| {
+        calls.fetch_add(1, Ordering::Relaxed);
+        let noise = if flip.fetch_xor(true, Ordering::Relaxed) {
+            -1.
+        } else {
+            1.
+        };
+        WithProduct::new(a * a + b * b + noise, ())
+    });
+    let f = Resample::new(f, 2);
+    assert_eq!(f.fitness(&[1., 2.]).ys(), 5.);
+    let pool = [alloc::vec![1., 2.], alloc::vec![0., 3.]];
+    let ys = f.fitness_batch(&pool, &Progress::default());
+    assert_eq!(ys.iter().map(|ys| ys.ys()).sum::<f64>(), 14.);
+    assert_eq!(calls.load(Ordering::Relaxed), 6);
+    // The same resampling by the builder
+    calls.store(0, Ordering::Relaxed);
+    let f = Fx::new(&bound, |&[a, b]| {
+        calls.fetch_add(1, Ordering::Relaxed);
+        a * a + b * b
+    });
+    let mut evals = 0;
+    let _ = Solver::build(Rga::default(), f)
+        .seed(0)
+        .pop_num(10)
+        .task(|ctx| ctx.gen == 5)
+        .callback(|ctx| evals = ctx.eval_count())
+        .resample(3)
+        .solve()
+        .unwrap();
+    assert!(evals > 0);
+    assert_eq!(calls.load(Ordering::Relaxed), 3 * evals);
+}
+
+#[test]
+fn constrained_fit() {
+    let fit = |ys, violation| ConstrainedFit { ys, violation };
+    // Feasible beats infeasible
+    assert!(fit(9., 0.).is_dominated(&fit(1., 0.5)));
+    assert!(!fit(1., 0.5).is_dominated(&fit(9., 0.)));
+    // Smaller violation wins among infeasible
+    assert!(fit(9., 0.1).is_dominated(&fit(1., 0.5)));
+    // Smaller objective wins among feasible
+    assert!(fit(1., 0.).is_dominated(&fit(9., 0.)));
+}